@@ -1,15 +1,61 @@
-use std::error::Error;
+use std::{error::Error, time::Duration};
+
+use serde::{Deserialize, Serialize};
+
+use crate::models::Violation;
 
 #[derive(Debug)]
 pub enum WhatsappError {
     ReqwestError(reqwest::Error),
+    Api(Box<ApiError>),
+    ConfigurationError(String),
+    IoError(std::io::Error),
+    MediaVerificationError(MediaVerificationError),
+    ValidationError(Vec<Violation>),
     UnexpectedError(String),
 }
 
+impl WhatsappError {
+    pub fn api_error(&self) -> Option<&ApiError> {
+        match self {
+            WhatsappError::Api(e) => Some(e.as_ref()),
+            _ => None,
+        }
+    }
+
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            WhatsappError::ReqwestError(e) => e.is_timeout() || e.is_connect(),
+            WhatsappError::Api(e) => e.is_retryable(),
+            WhatsappError::ConfigurationError(_)
+            | WhatsappError::IoError(_)
+            | WhatsappError::MediaVerificationError(_)
+            | WhatsappError::ValidationError(_)
+            | WhatsappError::UnexpectedError(_) => false,
+        }
+    }
+
+    pub fn is_auth_error(&self) -> bool {
+        self.api_error().is_some_and(ApiError::is_auth_error)
+    }
+
+    pub fn is_rate_limited(&self) -> bool {
+        self.api_error().is_some_and(ApiError::is_rate_limited)
+    }
+}
+
 impl std::fmt::Display for WhatsappError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             WhatsappError::ReqwestError(e) => f.write_str(e.to_string().as_str()),
+            WhatsappError::Api(e) => e.fmt(f),
+            WhatsappError::ConfigurationError(e) => f.write_str(e.as_str()),
+            WhatsappError::IoError(e) => e.fmt(f),
+            WhatsappError::MediaVerificationError(e) => e.fmt(f),
+            WhatsappError::ValidationError(violations) => {
+                let violations: Vec<String> = violations.iter().map(|v| v.to_string()).collect();
+                f.write_str(violations.join("; ").as_str())
+            }
             WhatsappError::UnexpectedError(e) => f.write_str(e.to_string().as_str()),
         }
     }
@@ -19,13 +65,202 @@ impl Error for WhatsappError {
     fn source(&self) -> Option<&(dyn Error + 'static)> {
         match self {
             WhatsappError::ReqwestError(e) => Some(e),
-            WhatsappError::UnexpectedError(_) => None,
+            WhatsappError::Api(e) => Some(e.as_ref()),
+            WhatsappError::IoError(e) => Some(e),
+            WhatsappError::MediaVerificationError(e) => Some(e),
+            WhatsappError::ConfigurationError(_)
+            | WhatsappError::ValidationError(_)
+            | WhatsappError::UnexpectedError(_) => None,
         }
     }
 }
 
 impl From<reqwest::Error> for WhatsappError {
-  fn from(e: reqwest::Error) -> Self {
-    WhatsappError::ReqwestError(e)
-  }
+    fn from(e: reqwest::Error) -> Self {
+        WhatsappError::ReqwestError(e)
+    }
+}
+
+impl From<std::io::Error> for WhatsappError {
+    fn from(e: std::io::Error) -> Self {
+        WhatsappError::IoError(e)
+    }
+}
+
+impl From<ApiError> for WhatsappError {
+    fn from(e: ApiError) -> Self {
+        WhatsappError::Api(Box::new(e))
+    }
+}
+
+/// Downloaded media does not match the size or digest announced by the Graph API
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MediaVerificationError {
+    FileSizeMismatch { expected: u64, actual: u64 },
+    Sha256Mismatch { expected: String, actual: String },
+}
+
+impl std::fmt::Display for MediaVerificationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MediaVerificationError::FileSizeMismatch { expected, actual } => {
+                write!(
+                    f,
+                    "media file size mismatch: expected {expected} bytes, got {actual}"
+                )
+            }
+            MediaVerificationError::Sha256Mismatch { expected, actual } => {
+                write!(
+                    f,
+                    "media sha256 mismatch: expected {expected}, got {actual}"
+                )
+            }
+        }
+    }
 }
+
+impl Error for MediaVerificationError {}
+
+/// Error returned by the Graph API, parsed from the `{"error": {...}}` envelope
+/// of a non-successful response.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ApiError {
+    #[serde(skip)]
+    pub status: u16,
+    /// Value of the `Retry-After` response header, if any
+    #[serde(skip)]
+    pub retry_after: Option<Duration>,
+    pub message: String,
+    #[serde(rename = "type")]
+    pub error_type: Option<String>,
+    pub code: i32,
+    pub error_subcode: Option<i32>,
+    pub error_user_title: Option<String>,
+    pub error_user_msg: Option<String>,
+    pub error_data: Option<ApiErrorData>,
+    pub fbtrace_id: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ApiErrorData {
+    pub messaging_product: Option<String>,
+    pub details: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct ApiErrorResponse {
+    pub error: ApiError,
+}
+
+/// Broad classes of the error codes documented for the Cloud API
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ApiErrorCategory {
+    Authorization,
+    Throttling,
+    Integrity,
+    Parameter,
+    Recipient,
+    Media,
+    Template,
+    Registration,
+    Server,
+}
+
+/// Documented Cloud API error codes and the category each one falls into.
+/// Codes 200-299 (API permission) are handled separately as a range.
+const ERROR_CODES: &[(i32, ApiErrorCategory)] = &[
+    (0, ApiErrorCategory::Authorization),
+    (3, ApiErrorCategory::Authorization),
+    (10, ApiErrorCategory::Authorization),
+    (190, ApiErrorCategory::Authorization),
+    (4, ApiErrorCategory::Throttling),
+    (80007, ApiErrorCategory::Throttling),
+    (130429, ApiErrorCategory::Throttling),
+    (131048, ApiErrorCategory::Throttling),
+    (131056, ApiErrorCategory::Throttling),
+    (368, ApiErrorCategory::Integrity),
+    (130497, ApiErrorCategory::Integrity),
+    (131031, ApiErrorCategory::Integrity),
+    (33, ApiErrorCategory::Parameter),
+    (100, ApiErrorCategory::Parameter),
+    (130472, ApiErrorCategory::Parameter),
+    (131008, ApiErrorCategory::Parameter),
+    (131009, ApiErrorCategory::Parameter),
+    (131021, ApiErrorCategory::Parameter),
+    (131051, ApiErrorCategory::Parameter),
+    (131026, ApiErrorCategory::Recipient),
+    (131030, ApiErrorCategory::Recipient),
+    (131047, ApiErrorCategory::Recipient),
+    (131050, ApiErrorCategory::Recipient),
+    (131052, ApiErrorCategory::Media),
+    (131053, ApiErrorCategory::Media),
+    (132000, ApiErrorCategory::Template),
+    (132001, ApiErrorCategory::Template),
+    (132005, ApiErrorCategory::Template),
+    (132007, ApiErrorCategory::Template),
+    (132012, ApiErrorCategory::Template),
+    (132015, ApiErrorCategory::Template),
+    (132016, ApiErrorCategory::Template),
+    (132068, ApiErrorCategory::Template),
+    (132069, ApiErrorCategory::Template),
+    (133000, ApiErrorCategory::Registration),
+    (133005, ApiErrorCategory::Registration),
+    (133006, ApiErrorCategory::Registration),
+    (133008, ApiErrorCategory::Registration),
+    (133009, ApiErrorCategory::Registration),
+    (133010, ApiErrorCategory::Registration),
+    (133015, ApiErrorCategory::Registration),
+    (133016, ApiErrorCategory::Registration),
+    (1, ApiErrorCategory::Server),
+    (2, ApiErrorCategory::Server),
+    (131000, ApiErrorCategory::Server),
+    (131016, ApiErrorCategory::Server),
+    (131057, ApiErrorCategory::Server),
+    (133004, ApiErrorCategory::Server),
+];
+
+impl ApiError {
+    pub fn category(&self) -> Option<ApiErrorCategory> {
+        if (200..=299).contains(&self.code) {
+            return Some(ApiErrorCategory::Authorization);
+        }
+
+        ERROR_CODES
+            .iter()
+            .find(|(code, _)| *code == self.code)
+            .map(|(_, category)| *category)
+    }
+
+    pub fn details(&self) -> Option<&str> {
+        self.error_data.as_ref()?.details.as_deref()
+    }
+
+    /// Whether sending the same request again later may succeed
+    pub fn is_retryable(&self) -> bool {
+        self.status >= 500
+            || matches!(
+                self.category(),
+                Some(ApiErrorCategory::Throttling | ApiErrorCategory::Server)
+            )
+    }
+
+    pub fn is_auth_error(&self) -> bool {
+        self.status == 401 || self.category() == Some(ApiErrorCategory::Authorization)
+    }
+
+    pub fn is_rate_limited(&self) -> bool {
+        self.status == 429 || self.category() == Some(ApiErrorCategory::Throttling)
+    }
+}
+
+impl std::fmt::Display for ApiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}) {}", self.code, self.message)?;
+        if let Some(details) = self.details() {
+            write!(f, ": {details}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for ApiError {}
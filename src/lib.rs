@@ -1,8 +1,15 @@
 mod error;
 pub mod models;
+mod retry;
 mod whatsapp_client;
+mod whatsapp_client_builder;
 
+pub use crate::retry::RetryPolicy;
 pub use crate::whatsapp_client::WhatsappClient;
-pub use error::WhatsappError;
+pub use crate::whatsapp_client_builder::WhatsappClientBuilder;
+pub use error::{ApiError, ApiErrorCategory, ApiErrorData, MediaVerificationError, WhatsappError};
+
+#[cfg(feature = "derive")]
+pub use whatsapp_business_cloud_api_derive::WhatsappTemplate;
 
 pub const WHATSAPP: &str = "whatsapp";
@@ -14,6 +14,9 @@ pub struct BusinessProfileData {
     #[serde(default = "default_messaging_product")]
     pub messaging_product: String,
     pub profile_picture_url: Option<String>,
+    /// Handle returned by the Resumable Upload API, to update the profile picture
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub profile_picture_handle: Option<String>,
     pub vertical: Option<String>,
     pub websites: Option<Vec<String>>,
 }
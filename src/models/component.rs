@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use super::{interactive_message::InteractiveActionSection, location_message::Location};
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Component {
     #[serde(rename = "type")]
@@ -7,6 +9,7 @@ pub struct Component {
     pub sub_type: Option<ComponentSubType>,
     pub parameters: Option<Vec<Parameter>>,
     pub index: Option<i32>,
+    pub cards: Option<Vec<CarouselCard>>,
 }
 
 impl Component {
@@ -16,6 +19,7 @@ impl Component {
             sub_type: None,
             parameters: None,
             index: None,
+            cards: None,
         }
     }
 
@@ -25,6 +29,7 @@ impl Component {
             sub_type: None,
             parameters: Some(parameters),
             index: None,
+            cards: None,
         }
     }
 
@@ -39,6 +44,34 @@ impl Component {
             sub_type: Some(sub_type),
             parameters: Some(parameters),
             index: Some(index),
+            cards: None,
+        }
+    }
+
+    /// Carousel of a media card carousel template
+    pub fn carousel(cards: Vec<CarouselCard>) -> Self {
+        Self {
+            component_type: ComponentType::Carousel,
+            sub_type: None,
+            parameters: None,
+            index: None,
+            cards: Some(cards),
+        }
+    }
+}
+
+/// Card of a carousel template, with its own header, body and button components
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CarouselCard {
+    pub card_index: i32,
+    pub components: Vec<Component>,
+}
+
+impl CarouselCard {
+    pub fn new(card_index: i32, components: Vec<Component>) -> Self {
+        Self {
+            card_index,
+            components,
         }
     }
 }
@@ -49,43 +82,152 @@ pub enum ComponentType {
     Header,
     Body,
     Button,
+    Carousel,
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub enum ComponentSubType {
     QuickReply,
     Url,
     Catalog,
+    CopyCode,
+    Flow,
+    Mpm,
+    Otp,
+}
+
+impl ComponentSubType {
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            ComponentSubType::QuickReply => "quick_reply",
+            ComponentSubType::Url => "url",
+            ComponentSubType::Catalog => "catalog",
+            ComponentSubType::CopyCode => "copy_code",
+            ComponentSubType::Flow => "flow",
+            ComponentSubType::Mpm => "mpm",
+            ComponentSubType::Otp => "otp",
+        }
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Parameter {
     #[serde(rename = "type")]
     pub parameter_type: ParameterType,
+    /// Name of the parameter, for templates using named parameters (`{{first_name}}`)
+    pub parameter_name: Option<String>,
     pub text: Option<String>,
     pub currency: Option<Currency>,
-    pub date_time: Option<String>,
+    pub date_time: Option<DateTime>,
     pub image: Option<Media>,
     pub document: Option<Media>,
     pub video: Option<Media>,
+    pub location: Option<Location>,
+    pub payload: Option<String>,
+    pub coupon_code: Option<String>,
+    pub action: Option<ParameterAction>,
 }
 
 impl Parameter {
-    pub fn from_text(text: &str) -> Self {
+    fn new(parameter_type: ParameterType) -> Self {
         Self {
-            parameter_type: ParameterType::Text,
-            text: Some(text.into()),
+            parameter_type,
+            parameter_name: None,
+            text: None,
             currency: None,
             date_time: None,
             image: None,
             document: None,
             video: None,
+            location: None,
+            payload: None,
+            coupon_code: None,
+            action: None,
+        }
+    }
+
+    pub fn from_text(text: &str) -> Self {
+        Self {
+            text: Some(text.into()),
+            ..Self::new(ParameterType::Text)
+        }
+    }
+
+    pub fn from_currency(currency: Currency) -> Self {
+        Self {
+            currency: Some(currency),
+            ..Self::new(ParameterType::Currency)
+        }
+    }
+
+    pub fn from_date_time(date_time: DateTime) -> Self {
+        Self {
+            date_time: Some(date_time),
+            ..Self::new(ParameterType::DateTime)
+        }
+    }
+
+    pub fn from_image(image: Media) -> Self {
+        Self {
+            image: Some(image),
+            ..Self::new(ParameterType::Image)
+        }
+    }
+
+    pub fn from_document(document: Media) -> Self {
+        Self {
+            document: Some(document),
+            ..Self::new(ParameterType::Document)
+        }
+    }
+
+    pub fn from_video(video: Media) -> Self {
+        Self {
+            video: Some(video),
+            ..Self::new(ParameterType::Video)
+        }
+    }
+
+    /// Location header parameter
+    pub fn from_location(location: Location) -> Self {
+        Self {
+            location: Some(location),
+            ..Self::new(ParameterType::Location)
+        }
+    }
+
+    /// Payload of a quick reply button, returned in the button webhook
+    pub fn from_payload(payload: &str) -> Self {
+        Self {
+            payload: Some(payload.into()),
+            ..Self::new(ParameterType::Payload)
+        }
+    }
+
+    /// Code of a copy code button
+    pub fn from_coupon_code(coupon_code: &str) -> Self {
+        Self {
+            coupon_code: Some(coupon_code.into()),
+            ..Self::new(ParameterType::CouponCode)
         }
     }
+
+    /// Action of a catalog, multi-product or flow button
+    pub fn from_action(action: ParameterAction) -> Self {
+        Self {
+            action: Some(action),
+            ..Self::new(ParameterType::Action)
+        }
+    }
+
+    pub fn with_parameter_name(mut self, parameter_name: &str) -> Self {
+        self.parameter_name = Some(parameter_name.into());
+        self
+    }
 }
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub enum ParameterType {
     Currency,
@@ -95,6 +237,26 @@ pub enum ParameterType {
     Text,
     Video,
     Payload,
+    Location,
+    CouponCode,
+    Action,
+}
+
+impl ParameterType {
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            ParameterType::Currency => "currency",
+            ParameterType::DateTime => "date_time",
+            ParameterType::Document => "document",
+            ParameterType::Image => "image",
+            ParameterType::Text => "text",
+            ParameterType::Video => "video",
+            ParameterType::Payload => "payload",
+            ParameterType::Location => "location",
+            ParameterType::CouponCode => "coupon_code",
+            ParameterType::Action => "action",
+        }
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -104,11 +266,30 @@ pub struct Currency {
     pub amount_1000: i32,
 }
 
+impl Currency {
+    /// `amount_1000` is the amount multiplied by 1000, e.g. 100990 for 100.99
+    pub fn new(fallback_value: &str, code: &str, amount_1000: i32) -> Self {
+        Self {
+            fallback_value: fallback_value.into(),
+            code: code.into(),
+            amount_1000,
+        }
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct DateTime {
     pub fallback_value: String,
 }
 
+impl DateTime {
+    pub fn new(fallback_value: &str) -> Self {
+        Self {
+            fallback_value: fallback_value.into(),
+        }
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Media {
     pub id: Option<String>,
@@ -116,3 +297,65 @@ pub struct Media {
     pub caption: Option<String>,
     pub filename: Option<String>,
 }
+
+impl Media {
+    pub fn new(link: &str) -> Self {
+        Self {
+            id: None,
+            link: Some(link.into()),
+            caption: None,
+            filename: None,
+        }
+    }
+
+    pub fn for_id(id: &str) -> Self {
+        Self {
+            id: Some(id.into()),
+            link: None,
+            caption: None,
+            filename: None,
+        }
+    }
+}
+
+/// Action of a catalog, multi-product (MPM) or flow template button
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ParameterAction {
+    pub thumbnail_product_retailer_id: Option<String>,
+    pub sections: Option<Vec<InteractiveActionSection>>,
+    pub flow_token: Option<String>,
+    pub flow_action_data: Option<serde_json::Value>,
+}
+
+impl ParameterAction {
+    pub fn for_catalog(thumbnail_product_retailer_id: &str) -> Self {
+        Self {
+            thumbnail_product_retailer_id: Some(thumbnail_product_retailer_id.into()),
+            sections: None,
+            flow_token: None,
+            flow_action_data: None,
+        }
+    }
+
+    /// Sections must be built with `InteractiveActionSection::with_product_items`
+    pub fn for_mpm(
+        thumbnail_product_retailer_id: &str,
+        sections: Vec<InteractiveActionSection>,
+    ) -> Self {
+        Self {
+            thumbnail_product_retailer_id: Some(thumbnail_product_retailer_id.into()),
+            sections: Some(sections),
+            flow_token: None,
+            flow_action_data: None,
+        }
+    }
+
+    pub fn for_flow(flow_token: &str, flow_action_data: Option<serde_json::Value>) -> Self {
+        Self {
+            thumbnail_product_retailer_id: None,
+            sections: None,
+            flow_token: Some(flow_token.into()),
+            flow_action_data,
+        }
+    }
+}
@@ -0,0 +1,147 @@
+use serde::{Deserialize, Serialize};
+
+/// A contact card (vCard-like) sent with a contacts message
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ContactCard {
+    pub addresses: Option<Vec<ContactAddress>>,
+    pub birthday: Option<String>,
+    pub emails: Option<Vec<ContactEmail>>,
+    pub name: ContactName,
+    pub org: Option<ContactOrg>,
+    pub phones: Option<Vec<ContactPhone>>,
+    pub urls: Option<Vec<ContactUrl>>,
+}
+
+impl ContactCard {
+    pub fn new(name: ContactName) -> Self {
+        Self {
+            addresses: None,
+            birthday: None,
+            emails: None,
+            name,
+            org: None,
+            phones: None,
+            urls: None,
+        }
+    }
+
+    pub fn with_phones(name: ContactName, phones: Vec<ContactPhone>) -> Self {
+        Self {
+            phones: Some(phones),
+            ..Self::new(name)
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ContactName {
+    pub formatted_name: String,
+    pub first_name: Option<String>,
+    pub last_name: Option<String>,
+    pub middle_name: Option<String>,
+    pub suffix: Option<String>,
+    pub prefix: Option<String>,
+}
+
+impl ContactName {
+    pub fn new(formatted_name: &str) -> Self {
+        Self {
+            formatted_name: formatted_name.into(),
+            first_name: None,
+            last_name: None,
+            middle_name: None,
+            suffix: None,
+            prefix: None,
+        }
+    }
+
+    pub fn with_first_and_last_name(
+        formatted_name: &str,
+        first_name: &str,
+        last_name: &str,
+    ) -> Self {
+        Self {
+            first_name: Some(first_name.into()),
+            last_name: Some(last_name.into()),
+            ..Self::new(formatted_name)
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ContactPhone {
+    pub phone: String,
+    pub wa_id: Option<String>,
+    #[serde(rename = "type")]
+    pub phone_type: Option<String>,
+}
+
+impl ContactPhone {
+    pub fn new(phone: &str, phone_type: Option<String>) -> Self {
+        Self {
+            phone: phone.into(),
+            wa_id: None,
+            phone_type,
+        }
+    }
+
+    /// Phone with a WhatsApp id, which adds a "Message" button to the contact card
+    pub fn with_wa_id(phone: &str, wa_id: &str, phone_type: Option<String>) -> Self {
+        Self {
+            phone: phone.into(),
+            wa_id: Some(wa_id.into()),
+            phone_type,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ContactEmail {
+    pub email: String,
+    #[serde(rename = "type")]
+    pub email_type: Option<String>,
+}
+
+impl ContactEmail {
+    pub fn new(email: &str, email_type: Option<String>) -> Self {
+        Self {
+            email: email.into(),
+            email_type,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ContactAddress {
+    pub street: Option<String>,
+    pub city: Option<String>,
+    pub state: Option<String>,
+    pub zip: Option<String>,
+    pub country: Option<String>,
+    pub country_code: Option<String>,
+    #[serde(rename = "type")]
+    pub address_type: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ContactOrg {
+    pub company: Option<String>,
+    pub department: Option<String>,
+    pub title: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ContactUrl {
+    pub url: String,
+    #[serde(rename = "type")]
+    pub url_type: Option<String>,
+}
+
+impl ContactUrl {
+    pub fn new(url: &str, url_type: Option<String>) -> Self {
+        Self {
+            url: url.into(),
+            url_type,
+        }
+    }
+}
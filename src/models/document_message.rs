@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Document {
+    link: Option<String>,
+    id: Option<String>,
+    caption: Option<String>,
+    filename: Option<String>,
+}
+
+impl Document {
+    pub fn new(link: &str, caption: Option<String>, filename: Option<String>) -> Self {
+        Self {
+            link: Some(link.into()),
+            id: None,
+            caption,
+            filename,
+        }
+    }
+
+    pub fn for_id(id: &str, caption: Option<String>, filename: Option<String>) -> Self {
+        Self {
+            link: None,
+            id: Some(id.into()),
+            caption,
+            filename,
+        }
+    }
+}
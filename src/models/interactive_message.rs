@@ -1,9 +1,38 @@
 use serde::{Deserialize, Serialize};
 
+use crate::WhatsappError;
+
+use super::{
+    document_message::Document,
+    image_message::Image,
+    validation::{check_count, check_length, Violation},
+    video_message::Video,
+};
+
+const MAX_BODY_TEXT_LENGTH: usize = 1024;
+const MAX_HEADER_TEXT_LENGTH: usize = 60;
+const MAX_FOOTER_TEXT_LENGTH: usize = 60;
+const MAX_BUTTONS: usize = 3;
+const MAX_BUTTON_TITLE_LENGTH: usize = 20;
+const MAX_BUTTON_ID_LENGTH: usize = 256;
+const MAX_LIST_BUTTON_LENGTH: usize = 20;
+const MAX_LIST_SECTIONS: usize = 10;
+const MAX_LIST_ROWS: usize = 10;
+const MAX_SECTION_TITLE_LENGTH: usize = 24;
+const MAX_ROW_ID_LENGTH: usize = 200;
+const MAX_ROW_TITLE_LENGTH: usize = 24;
+const MAX_ROW_DESCRIPTION_LENGTH: usize = 72;
+const MAX_PRODUCT_LIST_SECTIONS: usize = 10;
+const MAX_PRODUCT_LIST_ITEMS: usize = 30;
+const MAX_CTA_LENGTH: usize = 20;
+const FLOW_MESSAGE_VERSION: &str = "3";
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct Interactive {
     action: InteractiveAction,
     body: Option<InteractiveBody>,
+    footer: Option<InteractiveFooter>,
+    header: Option<InteractiveHeader>,
 
     #[serde(rename = "type")]
     interactive_type: InteractiveType,
@@ -15,6 +44,8 @@ impl Interactive {
             action: InteractiveAction::new_buttons(buttons),
             interactive_type: InteractiveType::Button,
             body: Some(InteractiveBody::new(body_text)),
+            footer: None,
+            header: None,
         }
     }
 
@@ -27,14 +58,253 @@ impl Interactive {
             action: InteractiveAction::new_list(button, sections),
             interactive_type: InteractiveType::List,
             body: Some(InteractiveBody::new(body_text)),
+            footer: None,
+            header: None,
         }
     }
+
     pub fn for_catalog_messages(body_text: &str) -> Self {
         Self {
             interactive_type: InteractiveType::CatalogMessage,
             body: Some(InteractiveBody::new(body_text)),
             action: InteractiveAction::new_catalog_message(),
+            footer: None,
+            header: None,
+        }
+    }
+
+    pub fn for_product(catalog_id: &str, product_retailer_id: &str, body_text: &str) -> Self {
+        Self {
+            interactive_type: InteractiveType::Product,
+            body: Some(InteractiveBody::new(body_text)),
+            action: InteractiveAction::new_product(catalog_id, product_retailer_id),
+            footer: None,
+            header: None,
+        }
+    }
+
+    /// Multi-product message. Sections must be built with
+    /// `InteractiveActionSection::with_product_items`; at most 10 sections and
+    /// 30 products in total are allowed.
+    pub fn for_product_list(
+        catalog_id: &str,
+        header_text: &str,
+        sections: Vec<InteractiveActionSection>,
+        body_text: &str,
+    ) -> Result<Self, WhatsappError> {
+        let mut violations = Vec::new();
+        validate_product_sections("action.sections", &sections, &mut violations);
+        if !violations.is_empty() {
+            return Err(WhatsappError::ValidationError(violations));
         }
+
+        Ok(Self {
+            interactive_type: InteractiveType::ProductList,
+            body: Some(InteractiveBody::new(body_text)),
+            action: InteractiveAction::new_product_list(catalog_id, sections),
+            footer: None,
+            header: Some(InteractiveHeader::text(header_text)),
+        })
+    }
+
+    pub fn for_flow(parameters: FlowActionParameters, body_text: &str) -> Self {
+        Self {
+            interactive_type: InteractiveType::Flow,
+            body: Some(InteractiveBody::new(body_text)),
+            action: InteractiveAction::new_flow(parameters),
+            footer: None,
+            header: None,
+        }
+    }
+
+    /// Button opening `url`, labelled with `display_text`
+    pub fn for_cta_url(display_text: &str, url: &str, body_text: &str) -> Self {
+        Self {
+            interactive_type: InteractiveType::CtaUrl,
+            body: Some(InteractiveBody::new(body_text)),
+            action: InteractiveAction::new_cta_url(display_text, url),
+            footer: None,
+            header: None,
+        }
+    }
+
+    /// Asks the user to share their location, which is received as a location message
+    pub fn for_location_request(body_text: &str) -> Self {
+        Self {
+            interactive_type: InteractiveType::LocationRequestMessage,
+            body: Some(InteractiveBody::new(body_text)),
+            action: InteractiveAction::new_send_location(),
+            footer: None,
+            header: None,
+        }
+    }
+
+    pub fn with_header(mut self, header: InteractiveHeader) -> Self {
+        self.header = Some(header);
+        self
+    }
+
+    pub fn with_footer(mut self, footer: InteractiveFooter) -> Self {
+        self.footer = Some(footer);
+        self
+    }
+}
+
+impl Interactive {
+    pub(crate) fn validate(&self, field: &str, violations: &mut Vec<Violation>) {
+        if let Some(body) = &self.body {
+            check_length(
+                &format!("{field}.body.text"),
+                &body.text,
+                MAX_BODY_TEXT_LENGTH,
+                violations,
+            );
+        }
+        if let Some(footer) = &self.footer {
+            check_length(
+                &format!("{field}.footer.text"),
+                &footer.text,
+                MAX_FOOTER_TEXT_LENGTH,
+                violations,
+            );
+        }
+        if let Some(InteractiveHeader::Text { text }) = &self.header {
+            check_length(
+                &format!("{field}.header.text"),
+                text,
+                MAX_HEADER_TEXT_LENGTH,
+                violations,
+            );
+        }
+
+        let action = &self.action;
+        let field = format!("{field}.action");
+        match self.interactive_type {
+            InteractiveType::Button => {
+                let buttons = action.buttons.as_deref().unwrap_or_default();
+                check_count(
+                    &format!("{field}.buttons"),
+                    buttons.len(),
+                    MAX_BUTTONS,
+                    violations,
+                );
+                for (i, button) in buttons.iter().enumerate() {
+                    button.validate(&format!("{field}.buttons[{i}]"), violations);
+                }
+            }
+            InteractiveType::List => {
+                check_length(
+                    &format!("{field}.button"),
+                    action.button.as_deref().unwrap_or_default(),
+                    MAX_LIST_BUTTON_LENGTH,
+                    violations,
+                );
+                let sections = action.sections.as_deref().unwrap_or_default();
+                check_count(
+                    &format!("{field}.sections"),
+                    sections.len(),
+                    MAX_LIST_SECTIONS,
+                    violations,
+                );
+                let rows = sections
+                    .iter()
+                    .map(|section| section.rows.as_ref().map_or(0, Vec::len))
+                    .sum();
+                check_count(
+                    &format!("{field}.sections[].rows"),
+                    rows,
+                    MAX_LIST_ROWS,
+                    violations,
+                );
+                for (i, section) in sections.iter().enumerate() {
+                    section.validate(&format!("{field}.sections[{i}]"), violations);
+                }
+            }
+            InteractiveType::ProductList => {
+                let sections = action.sections.as_deref().unwrap_or_default();
+                validate_product_sections(&format!("{field}.sections"), sections, violations);
+            }
+            InteractiveType::CtaUrl => {
+                if let Some(InteractiveActionParameters::CtaUrl { display_text, .. }) =
+                    &action.parameters
+                {
+                    check_length(
+                        &format!("{field}.parameters.display_text"),
+                        display_text,
+                        MAX_CTA_LENGTH,
+                        violations,
+                    );
+                }
+            }
+            InteractiveType::Flow => {
+                if let Some(InteractiveActionParameters::Flow(parameters)) = &action.parameters {
+                    check_length(
+                        &format!("{field}.parameters.flow_cta"),
+                        &parameters.flow_cta,
+                        MAX_CTA_LENGTH,
+                        violations,
+                    );
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn validate_product_sections(
+    field: &str,
+    sections: &[InteractiveActionSection],
+    violations: &mut Vec<Violation>,
+) {
+    check_count(field, sections.len(), MAX_PRODUCT_LIST_SECTIONS, violations);
+    let product_items = sections
+        .iter()
+        .map(|section| section.product_items.as_ref().map_or(0, Vec::len))
+        .sum();
+    check_count(
+        &format!("{field}[].product_items"),
+        product_items,
+        MAX_PRODUCT_LIST_ITEMS,
+        violations,
+    );
+}
+
+/// Header of an interactive message. Media headers are only supported by button messages
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum InteractiveHeader {
+    Text { text: String },
+    Image { image: Image },
+    Video { video: Video },
+    Document { document: Document },
+}
+
+impl InteractiveHeader {
+    pub fn text(text: &str) -> Self {
+        Self::Text { text: text.into() }
+    }
+
+    pub fn image(image: Image) -> Self {
+        Self::Image { image }
+    }
+
+    pub fn video(video: Video) -> Self {
+        Self::Video { video }
+    }
+
+    pub fn document(document: Document) -> Self {
+        Self::Document { document }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct InteractiveFooter {
+    text: String,
+}
+
+impl InteractiveFooter {
+    pub fn new(text: &str) -> Self {
+        Self { text: text.into() }
     }
 }
 
@@ -75,6 +345,69 @@ impl InteractiveAction {
         }
     }
 
+    pub fn new_product(catalog_id: &str, product_retailer_id: &str) -> Self {
+        InteractiveAction {
+            button: None,
+            buttons: None,
+            catalog_id: Some(catalog_id.into()),
+            product_retailer_id: Some(product_retailer_id.into()),
+            sections: None,
+            name: None,
+            parameters: None,
+        }
+    }
+
+    pub fn new_product_list(catalog_id: &str, sections: Vec<InteractiveActionSection>) -> Self {
+        InteractiveAction {
+            button: None,
+            buttons: None,
+            catalog_id: Some(catalog_id.into()),
+            product_retailer_id: None,
+            sections: Some(sections),
+            name: None,
+            parameters: None,
+        }
+    }
+
+    pub fn new_flow(parameters: FlowActionParameters) -> Self {
+        InteractiveAction {
+            name: Some("flow".into()),
+            parameters: Some(InteractiveActionParameters::Flow(parameters)),
+            button: None,
+            buttons: None,
+            catalog_id: None,
+            product_retailer_id: None,
+            sections: None,
+        }
+    }
+
+    pub fn new_cta_url(display_text: &str, url: &str) -> Self {
+        InteractiveAction {
+            name: Some("cta_url".into()),
+            parameters: Some(InteractiveActionParameters::CtaUrl {
+                display_text: display_text.into(),
+                url: url.into(),
+            }),
+            button: None,
+            buttons: None,
+            catalog_id: None,
+            product_retailer_id: None,
+            sections: None,
+        }
+    }
+
+    pub fn new_send_location() -> Self {
+        InteractiveAction {
+            name: Some("send_location".into()),
+            parameters: None,
+            button: None,
+            buttons: None,
+            catalog_id: None,
+            product_retailer_id: None,
+            sections: None,
+        }
+    }
+
     pub fn new_catalog_message() -> Self {
         InteractiveAction {
             name: Some("catalog_message".into()),
@@ -125,6 +458,23 @@ impl InteractiveActionButton {
     }
 }
 
+impl InteractiveActionButton {
+    pub(crate) fn validate(&self, field: &str, violations: &mut Vec<Violation>) {
+        check_length(
+            &format!("{field}.reply.title"),
+            &self.reply.title,
+            MAX_BUTTON_TITLE_LENGTH,
+            violations,
+        );
+        check_length(
+            &format!("{field}.reply.id"),
+            &self.reply.id,
+            MAX_BUTTON_ID_LENGTH,
+            violations,
+        );
+    }
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(rename_all = "snake_case")]
 pub enum InteractiveActionButtonType {
@@ -140,32 +490,172 @@ pub enum InteractiveType {
     Product,
     ProductList,
     Flow,
+    CtaUrl,
+    LocationRequestMessage,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(untagged)]
+pub enum InteractiveActionParameters {
+    Flow(FlowActionParameters),
+    CtaUrl {
+        display_text: String,
+        url: String,
+    },
+    Catalog {
+        thumbnail_product_retailer_id: Option<String>,
+    },
+}
+
+/// Parameters of a WhatsApp Flows message. Either `flow_id` or `flow_name` must be set.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct FlowActionParameters {
+    pub flow_message_version: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub flow_token: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub flow_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub flow_name: Option<String>,
+    pub flow_cta: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub flow_action: Option<FlowAction>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub flow_action_payload: Option<FlowActionPayload>,
+    /// `draft` to send an unpublished flow, defaults to `published`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mode: Option<String>,
+}
+
+impl FlowActionParameters {
+    pub fn for_flow_id(flow_id: &str, flow_cta: &str) -> Self {
+        Self {
+            flow_message_version: FLOW_MESSAGE_VERSION.into(),
+            flow_token: None,
+            flow_id: Some(flow_id.into()),
+            flow_name: None,
+            flow_cta: flow_cta.into(),
+            flow_action: None,
+            flow_action_payload: None,
+            mode: None,
+        }
+    }
+
+    pub fn for_flow_name(flow_name: &str, flow_cta: &str) -> Self {
+        Self {
+            flow_message_version: FLOW_MESSAGE_VERSION.into(),
+            flow_token: None,
+            flow_id: None,
+            flow_name: Some(flow_name.into()),
+            flow_cta: flow_cta.into(),
+            flow_action: None,
+            flow_action_payload: None,
+            mode: None,
+        }
+    }
+
+    pub fn with_flow_token(mut self, flow_token: &str) -> Self {
+        self.flow_token = Some(flow_token.into());
+        self
+    }
+
+    /// Opens the flow on the given screen, with optional initial data
+    pub fn navigate(mut self, screen: &str, data: Option<serde_json::Value>) -> Self {
+        self.flow_action = Some(FlowAction::Navigate);
+        self.flow_action_payload = Some(FlowActionPayload {
+            screen: screen.into(),
+            data,
+        });
+        self
+    }
+
+    /// Opens the flow by requesting its first screen from the flow endpoint
+    pub fn data_exchange(mut self) -> Self {
+        self.flow_action = Some(FlowAction::DataExchange);
+        self.flow_action_payload = None;
+        self
+    }
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
-pub struct InteractiveActionParameters {
-    thumbnail_product_retailer_id: Option<String>,
+#[serde(rename_all = "snake_case")]
+pub enum FlowAction {
+    Navigate,
+    DataExchange,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct FlowActionPayload {
+    pub screen: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<serde_json::Value>,
 }
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct InteractiveActionSection {
-    // TODO: product_items
-    rows: Vec<InteractiveActionSectionRow>,
+    product_items: Option<Vec<InteractiveActionSectionProductItem>>,
+    rows: Option<Vec<InteractiveActionSectionRow>>,
     title: Option<String>,
 }
 
 impl InteractiveActionSection {
     pub fn new(rows: Vec<InteractiveActionSectionRow>) -> Self {
-        Self { rows, title: None }
+        Self {
+            product_items: None,
+            rows: Some(rows),
+            title: None,
+        }
     }
 
     pub fn with_title(rows: Vec<InteractiveActionSectionRow>, title: &str) -> Self {
         Self {
-            rows,
+            product_items: None,
+            rows: Some(rows),
+            title: Some(title.into()),
+        }
+    }
+
+    /// Section of a multi-product message
+    pub fn with_product_items(
+        product_items: Vec<InteractiveActionSectionProductItem>,
+        title: &str,
+    ) -> Self {
+        Self {
+            product_items: Some(product_items),
+            rows: None,
             title: Some(title.into()),
         }
     }
 }
 
+impl InteractiveActionSection {
+    pub(crate) fn validate(&self, field: &str, violations: &mut Vec<Violation>) {
+        if let Some(title) = &self.title {
+            check_length(
+                &format!("{field}.title"),
+                title,
+                MAX_SECTION_TITLE_LENGTH,
+                violations,
+            );
+        }
+        for (i, row) in self.rows.iter().flatten().enumerate() {
+            row.validate(&format!("{field}.rows[{i}]"), violations);
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct InteractiveActionSectionProductItem {
+    product_retailer_id: String,
+}
+
+impl InteractiveActionSectionProductItem {
+    pub fn new(product_retailer_id: &str) -> Self {
+        Self {
+            product_retailer_id: product_retailer_id.into(),
+        }
+    }
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct InteractiveActionSectionRow {
     id: String,
@@ -190,3 +680,28 @@ impl InteractiveActionSectionRow {
         }
     }
 }
+
+impl InteractiveActionSectionRow {
+    pub(crate) fn validate(&self, field: &str, violations: &mut Vec<Violation>) {
+        check_length(
+            &format!("{field}.id"),
+            &self.id,
+            MAX_ROW_ID_LENGTH,
+            violations,
+        );
+        check_length(
+            &format!("{field}.title"),
+            &self.title,
+            MAX_ROW_TITLE_LENGTH,
+            violations,
+        );
+        if let Some(description) = &self.description {
+            check_length(
+                &format!("{field}.description"),
+                description,
+                MAX_ROW_DESCRIPTION_LENGTH,
+                violations,
+            );
+        }
+    }
+}
@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Location {
+    pub latitude: f64,
+    pub longitude: f64,
+    pub name: Option<String>,
+    pub address: Option<String>,
+}
+
+impl Location {
+    pub fn new(latitude: f64, longitude: f64) -> Self {
+        Self {
+            latitude,
+            longitude,
+            name: None,
+            address: None,
+        }
+    }
+
+    pub fn with_name_and_address(latitude: f64, longitude: f64, name: &str, address: &str) -> Self {
+        Self {
+            latitude,
+            longitude,
+            name: Some(name.into()),
+            address: Some(address.into()),
+        }
+    }
+}
@@ -3,100 +3,179 @@ use serde::{Deserialize, Serialize};
 use crate::WHATSAPP;
 
 use super::{
-    image_message::Image, interactive_message::Interactive, template_message::Template,
-    text_message::Text,
+    audio_message::Audio,
+    contacts_message::ContactCard,
+    document_message::Document,
+    image_message::Image,
+    interactive_message::Interactive,
+    location_message::Location,
+    reaction_message::Reaction,
+    sticker_message::Sticker,
+    template_message::Template,
+    text_message::{split_body, Text, MAX_BODY_LENGTH},
+    validation::Violation,
+    video_message::Video,
 };
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct Message {
     biz_opaque_callback_data: Option<String>,
     context: Option<Context>,
-    image: Option<Image>,
-    interactive: Option<Interactive>,
     messaging_product: String,
     recipient_type: Option<String>,
     status: Option<StatusCode>,
-
-    template: Option<Template>,
-    text: Option<Text>,
     to: String,
 
-    #[serde(rename = "type")]
-    message_type: Option<MessageType>,
+    #[serde(flatten)]
+    content: MessageContent,
 }
 
+/// Payload of a message. Serializes as the `type` of the message together with
+/// the matching field, e.g. `"type": "text", "text": { ... }`.
 #[derive(Deserialize, Serialize, Debug, Clone)]
-#[serde(rename_all = "snake_case")]
-pub enum MessageType {
-    Image,
-    Interactive,
-    Text,
-    Template,
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MessageContent {
+    Audio { audio: Audio },
+    Contacts { contacts: Vec<ContactCard> },
+    Document { document: Document },
+    Image { image: Image },
+    Interactive { interactive: Box<Interactive> },
+    Location { location: Location },
+    Reaction { reaction: Reaction },
+    Sticker { sticker: Sticker },
+    Template { template: Template },
+    Text { text: Text },
+    Video { video: Video },
 }
 
 impl Message {
-    pub fn from_text(to: &str, text: Text, context: Option<Context>) -> Self {
+    pub fn new(to: &str, content: MessageContent) -> Self {
         Self {
             biz_opaque_callback_data: None,
-            context,
-            image: None,
-            interactive: None,
+            context: None,
             messaging_product: WHATSAPP.into(),
             recipient_type: None,
             status: None,
-            message_type: Some(MessageType::Text),
-            template: None,
-            text: Some(text),
             to: to.into(),
+            content,
         }
     }
 
+    fn with_optional_context(mut self, context: Option<Context>) -> Self {
+        self.context = context;
+        self
+    }
+
+    pub fn from_text(to: &str, text: Text, context: Option<Context>) -> Self {
+        Self::new(to, MessageContent::Text { text }).with_optional_context(context)
+    }
+
+    /// Splits a text longer than the 4096 characters limit into several text messages,
+    /// on paragraph, sentence or word boundaries. Formatting spans and grapheme
+    /// clusters are not cut. Only the first message replies to `context`.
+    pub fn split_text(to: &str, body: &str, context: Option<Context>) -> Vec<Self> {
+        let mut context = context;
+        split_body(body, MAX_BODY_LENGTH)
+            .into_iter()
+            .map(|chunk| Self::from_text(to, Text::new(chunk), context.take()))
+            .collect()
+    }
+
     pub fn from_template(to: &str, template: Template, context: Option<Context>) -> Self {
-        Self {
-            biz_opaque_callback_data: None,
-            context,
-            image: None,
-            interactive: None,
-            messaging_product: WHATSAPP.into(),
-            recipient_type: None,
-            status: None,
-            message_type: Some(MessageType::Template),
-            template: Some(template),
-            text: None,
-            to: to.into(),
-        }
+        Self::new(to, MessageContent::Template { template }).with_optional_context(context)
     }
 
     pub fn from_interactive(to: &str, interactive: Interactive, context: Option<Context>) -> Self {
-        Self {
-            biz_opaque_callback_data: None,
-            context,
-            image: None,
-            interactive: Some(interactive),
-            messaging_product: WHATSAPP.into(),
-            recipient_type: None,
-            status: None,
-            message_type: Some(MessageType::Interactive),
-            template: None,
-            text: None,
-            to: to.into(),
-        }
+        Self::new(
+            to,
+            MessageContent::Interactive {
+                interactive: Box::new(interactive),
+            },
+        )
+        .with_optional_context(context)
     }
 
     pub fn from_image(to: &str, image: Image, context: Option<Context>) -> Self {
-        Self {
-            biz_opaque_callback_data: None,
-            context,
-            image: Some(image),
-            interactive: None,
-            messaging_product: WHATSAPP.into(),
-            recipient_type: None,
-            status: None,
-            message_type: Some(MessageType::Image),
-            template: None,
-            text: None,
-            to: to.into(),
+        Self::new(to, MessageContent::Image { image }).with_optional_context(context)
+    }
+
+    pub fn from_audio(to: &str, audio: Audio, context: Option<Context>) -> Self {
+        Self::new(to, MessageContent::Audio { audio }).with_optional_context(context)
+    }
+
+    pub fn from_video(to: &str, video: Video, context: Option<Context>) -> Self {
+        Self::new(to, MessageContent::Video { video }).with_optional_context(context)
+    }
+
+    pub fn from_document(to: &str, document: Document, context: Option<Context>) -> Self {
+        Self::new(to, MessageContent::Document { document }).with_optional_context(context)
+    }
+
+    pub fn from_location(to: &str, location: Location, context: Option<Context>) -> Self {
+        Self::new(to, MessageContent::Location { location }).with_optional_context(context)
+    }
+
+    pub fn from_contacts(to: &str, contacts: Vec<ContactCard>, context: Option<Context>) -> Self {
+        Self::new(to, MessageContent::Contacts { contacts }).with_optional_context(context)
+    }
+
+    pub fn from_reaction(to: &str, message_id: &str, emoji: &str) -> Self {
+        let reaction = Reaction::new(message_id, emoji);
+        Self::new(to, MessageContent::Reaction { reaction })
+    }
+
+    pub fn remove_reaction(to: &str, message_id: &str) -> Self {
+        let reaction = Reaction::remove(message_id);
+        Self::new(to, MessageContent::Reaction { reaction })
+    }
+
+    pub fn from_sticker(to: &str, sticker: Sticker, context: Option<Context>) -> Self {
+        Self::new(to, MessageContent::Sticker { sticker }).with_optional_context(context)
+    }
+
+    /// Sends the message as a reply to the message with the given context
+    pub fn with_context(mut self, context: Context) -> Self {
+        self.context = Some(context);
+        self
+    }
+
+    /// Arbitrary string (up to 512 characters) returned in the status webhooks of this message
+    pub fn with_biz_opaque_callback_data(mut self, biz_opaque_callback_data: &str) -> Self {
+        self.biz_opaque_callback_data = Some(biz_opaque_callback_data.into());
+        self
+    }
+
+    pub fn with_recipient_type(mut self, recipient_type: &str) -> Self {
+        self.recipient_type = Some(recipient_type.into());
+        self
+    }
+
+    pub fn to(&self) -> &str {
+        &self.to
+    }
+
+    pub fn context(&self) -> Option<&Context> {
+        self.context.as_ref()
+    }
+
+    pub fn content(&self) -> &MessageContent {
+        &self.content
+    }
+
+    /// Checks the message against the limits of the Cloud API (lengths of texts,
+    /// number of buttons, rows and sections...), counting characters the way
+    /// WhatsApp does. Returns an empty list when the message is valid.
+    pub fn validate(&self) -> Vec<Violation> {
+        let mut violations = Vec::new();
+        match &self.content {
+            MessageContent::Text { text } => text.validate("text", &mut violations),
+            MessageContent::Interactive { interactive } => {
+                interactive.validate("interactive", &mut violations)
+            }
+            MessageContent::Template { template } => template.validate("template", &mut violations),
+            _ => {}
         }
+        violations
     }
 }
 
@@ -1,32 +1,51 @@
+mod audio_message;
 mod business_profile;
 mod component;
+mod contacts_message;
+mod document_message;
 mod image_message;
 mod interactive_message;
+mod location_message;
 mod media_response;
 mod message;
 mod message_response;
 mod phone_number;
 mod product_catalog;
+mod reaction_message;
+mod sticker_message;
+mod template_definition;
 mod template_message;
+mod template_render;
 mod text_message;
 mod upload;
+mod validation;
+mod video_message;
 
 pub mod webhooks;
 
+pub use audio_message::Audio;
 pub use business_profile::{
     BusinessProfileData, BusinessProfileResponse, ConnectCatalogToWhatsappBusiness,
     UpdateBusinessProfileResponse,
 };
 
 pub use component::{
-    Component, ComponentSubType, ComponentType, Currency, DateTime, Media, Parameter, ParameterType,
+    CarouselCard, Component, ComponentSubType, ComponentType, Currency, DateTime, Media, Parameter,
+    ParameterAction, ParameterType,
 };
+pub use contacts_message::{
+    ContactAddress, ContactCard, ContactEmail, ContactName, ContactOrg, ContactPhone, ContactUrl,
+};
+pub use document_message::Document;
 pub use image_message::Image;
 pub use interactive_message::{
-    Interactive, InteractiveActionButton, InteractiveActionSection, InteractiveActionSectionRow,
+    FlowAction, FlowActionParameters, FlowActionPayload, Interactive, InteractiveActionButton,
+    InteractiveActionSection, InteractiveActionSectionProductItem, InteractiveActionSectionRow,
+    InteractiveFooter, InteractiveHeader,
 };
+pub use location_message::Location;
 pub use media_response::MediaResponse;
-pub use message::{Context, Message, MessageStatus, StatusCode};
+pub use message::{Context, Message, MessageContent, MessageStatus, StatusCode};
 pub use message_response::{
     ContactResponse, CreatedMessage, MessageResponse, MessageStatusResponse,
 };
@@ -35,6 +54,20 @@ pub(crate) use phone_number::{CodeRequestParams, CodeVerifyParams};
 pub use product_catalog::{
     CreateProductCatalogRequest, EditItemProduct, ItemProduct, ProductCatalog, Success,
 };
+pub use reaction_message::Reaction;
+pub use sticker_message::Sticker;
+pub use template_definition::{
+    CreatedTemplate, Cursors, HeaderFormat, NamedParameterExample, OtpType, Paging,
+    ParameterFormat, TemplateButton, TemplateCarouselCard, TemplateCategory, TemplateComponent,
+    TemplateDefinition, TemplateEdit, TemplateExample, TemplateFilters, TemplateList,
+    TemplateStatus,
+};
 pub use template_message::{Language, Template};
-pub use text_message::Text;
-pub use upload::{UploadParams, UploadSuccess};
+pub use template_render::{RenderedButton, RenderedHeader, RenderedTemplate};
+pub use text_message::{FormattedText, Text};
+pub use upload::{
+    MediaSource, UploadMediaResponse, UploadParams, UploadSession, UploadSessionStatus,
+    UploadSuccess,
+};
+pub use validation::Violation;
+pub use video_message::Video;
@@ -0,0 +1,21 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Reaction {
+    message_id: String,
+    emoji: String,
+}
+
+impl Reaction {
+    pub fn new(message_id: &str, emoji: &str) -> Self {
+        Self {
+            message_id: message_id.into(),
+            emoji: emoji.into(),
+        }
+    }
+
+    /// Removes a previously sent reaction, by sending an empty emoji
+    pub fn remove(message_id: &str) -> Self {
+        Self::new(message_id, "")
+    }
+}
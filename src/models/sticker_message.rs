@@ -0,0 +1,23 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Sticker {
+    link: Option<String>,
+    id: Option<String>,
+}
+
+impl Sticker {
+    pub fn new(link: &str) -> Self {
+        Self {
+            link: Some(link.into()),
+            id: None,
+        }
+    }
+
+    pub fn for_id(id: &str) -> Self {
+        Self {
+            link: None,
+            id: Some(id.into()),
+        }
+    }
+}
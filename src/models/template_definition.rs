@@ -0,0 +1,451 @@
+use serde::{Deserialize, Deserializer, Serialize};
+
+/// Definition of a message template, as created with and returned by the
+/// message template management API (`/{waba_id}/message_templates`).
+/// This is the template itself, not a template message to send (see `Template`).
+/// It deserializes from the JSON returned by the API, so definitions can also be
+/// kept on disk and checked with `Template::validate_against` without an API call.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TemplateDefinition {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    pub name: String,
+    pub language: String,
+    pub category: TemplateCategory,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<TemplateStatus>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parameter_format: Option<ParameterFormat>,
+    pub components: Vec<TemplateComponent>,
+}
+
+impl TemplateDefinition {
+    pub fn new(
+        name: &str,
+        language: &str,
+        category: TemplateCategory,
+        components: Vec<TemplateComponent>,
+    ) -> Self {
+        Self {
+            id: None,
+            name: name.into(),
+            language: language.into(),
+            category,
+            status: None,
+            parameter_format: None,
+            components,
+        }
+    }
+
+    /// Authentication template with a copy code button, to be sent with
+    /// `Template::authentication`. The body text is set by WhatsApp. The expiry
+    /// warning is fixed here, in the footer, and not when the code is sent.
+    pub fn authentication(
+        name: &str,
+        language: &str,
+        code_expiration_minutes: Option<u32>,
+    ) -> Self {
+        let mut components = vec![TemplateComponent::Body {
+            text: None,
+            example: None,
+            add_security_recommendation: None,
+        }];
+        if let Some(minutes) = code_expiration_minutes {
+            components.push(TemplateComponent::Footer {
+                text: None,
+                code_expiration_minutes: Some(minutes),
+            });
+        }
+        components.push(TemplateComponent::buttons(vec![TemplateButton::Otp {
+            otp_type: OtpType::CopyCode,
+            text: None,
+            autofill_text: None,
+            package_name: None,
+            signature_hash: None,
+        }]));
+        Self::new(name, language, TemplateCategory::Authentication, components)
+    }
+
+    pub fn with_parameter_format(mut self, parameter_format: ParameterFormat) -> Self {
+        self.parameter_format = Some(parameter_format);
+        self
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum TemplateCategory {
+    Marketing,
+    Utility,
+    Authentication,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum TemplateStatus {
+    Approved,
+    Pending,
+    Rejected,
+    Paused,
+    Disabled,
+    InAppeal,
+    PendingDeletion,
+    Deleted,
+    LimitExceeded,
+    Archived,
+    /// Status not known to this crate
+    #[serde(other)]
+    Unknown,
+}
+
+/// Whether the template placeholders are positional (`{{1}}`) or named (`{{first_name}}`)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ParameterFormat {
+    Positional,
+    Named,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum TemplateComponent {
+    Header {
+        format: HeaderFormat,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        text: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        example: Option<TemplateExample>,
+    },
+    Body {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        text: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        example: Option<TemplateExample>,
+        /// Authentication templates only
+        #[serde(skip_serializing_if = "Option::is_none")]
+        add_security_recommendation: Option<bool>,
+    },
+    Footer {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        text: Option<String>,
+        /// Authentication templates only
+        #[serde(skip_serializing_if = "Option::is_none")]
+        code_expiration_minutes: Option<u32>,
+    },
+    Buttons {
+        buttons: Vec<TemplateButton>,
+    },
+    Carousel {
+        cards: Vec<TemplateCarouselCard>,
+    },
+    /// Component type not known to this crate, so that listing templates does not
+    /// fail on it. It is skipped by validation and rendering.
+    #[serde(other)]
+    Unknown,
+}
+
+impl TemplateComponent {
+    pub fn text_header(text: &str, example: Option<TemplateExample>) -> Self {
+        Self::Header {
+            format: HeaderFormat::Text,
+            text: Some(text.into()),
+            example,
+        }
+    }
+
+    /// Media (image, video, document) header. The example must contain a
+    /// `header_handle` obtained with the Resumable Upload API.
+    pub fn media_header(format: HeaderFormat, example: TemplateExample) -> Self {
+        Self::Header {
+            format,
+            text: None,
+            example: Some(example),
+        }
+    }
+
+    pub fn body(text: &str, example: Option<TemplateExample>) -> Self {
+        Self::Body {
+            text: Some(text.into()),
+            example,
+            add_security_recommendation: None,
+        }
+    }
+
+    pub fn footer(text: &str) -> Self {
+        Self::Footer {
+            text: Some(text.into()),
+            code_expiration_minutes: None,
+        }
+    }
+
+    pub fn buttons(buttons: Vec<TemplateButton>) -> Self {
+        Self::Buttons { buttons }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum HeaderFormat {
+    Text,
+    Image,
+    Video,
+    Document,
+    Location,
+    /// Header format not known to this crate
+    #[serde(other)]
+    Unknown,
+}
+
+/// Sample values of the template placeholders, required for review
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct TemplateExample {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub header_text: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub header_handle: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub header_text_named_params: Option<Vec<NamedParameterExample>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body_text: Option<Vec<Vec<String>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body_text_named_params: Option<Vec<NamedParameterExample>>,
+}
+
+impl TemplateExample {
+    pub fn for_header_text(header_text: Vec<String>) -> Self {
+        Self {
+            header_text: Some(header_text),
+            ..Default::default()
+        }
+    }
+
+    pub fn for_header_handle(header_handle: &str) -> Self {
+        Self {
+            header_handle: Some(vec![header_handle.into()]),
+            ..Default::default()
+        }
+    }
+
+    pub fn for_body_text(body_text: Vec<String>) -> Self {
+        Self {
+            body_text: Some(vec![body_text]),
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct NamedParameterExample {
+    pub param_name: String,
+    pub example: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum TemplateButton {
+    QuickReply {
+        text: String,
+    },
+    Url {
+        text: String,
+        url: String,
+        /// Example of the url suffix, when the url ends with a `{{1}}` placeholder
+        #[serde(skip_serializing_if = "Option::is_none")]
+        example: Option<Vec<String>>,
+    },
+    PhoneNumber {
+        text: String,
+        phone_number: String,
+    },
+    CopyCode {
+        /// The API returns the example either as a string or as a list of one string
+        #[serde(deserialize_with = "string_or_first")]
+        example: String,
+    },
+    Otp {
+        otp_type: OtpType,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        text: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        autofill_text: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        package_name: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        signature_hash: Option<String>,
+    },
+    Flow {
+        text: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        flow_id: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        flow_name: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        flow_action: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        navigate_screen: Option<String>,
+    },
+    Catalog {
+        text: String,
+    },
+    Mpm {
+        text: String,
+    },
+    /// Button type not known to this crate (e.g. `VOICE_CALL`). Its parameters are
+    /// not checked by validation and it is skipped by rendering.
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum OtpType {
+    CopyCode,
+    OneTap,
+    ZeroTap,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TemplateCarouselCard {
+    pub components: Vec<TemplateComponent>,
+}
+
+/// Changes to an existing template. Only the category and components can be edited.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct TemplateEdit {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category: Option<TemplateCategory>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub components: Option<Vec<TemplateComponent>>,
+}
+
+/// Query parameters of `list_message_templates`
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct TemplateFilters {
+    pub name: Option<String>,
+    pub status: Option<TemplateStatus>,
+    pub category: Option<TemplateCategory>,
+    pub language: Option<String>,
+    pub limit: Option<u32>,
+    pub after: Option<String>,
+    pub before: Option<String>,
+}
+
+impl TemplateFilters {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn name(mut self, name: &str) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    pub fn status(mut self, status: TemplateStatus) -> Self {
+        self.status = Some(status);
+        self
+    }
+
+    pub fn category(mut self, category: TemplateCategory) -> Self {
+        self.category = Some(category);
+        self
+    }
+
+    pub fn language(mut self, language: &str) -> Self {
+        self.language = Some(language.into());
+        self
+    }
+
+    pub fn limit(mut self, limit: u32) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Cursor of the next page, from `TemplateList::paging`
+    pub fn after(mut self, after: &str) -> Self {
+        self.after = Some(after.into());
+        self
+    }
+
+    pub fn before(mut self, before: &str) -> Self {
+        self.before = Some(before.into());
+        self
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TemplateList {
+    pub data: Vec<TemplateDefinition>,
+    pub paging: Option<Paging>,
+}
+
+impl TemplateList {
+    /// Cursor of the next page, if there is one
+    pub fn next_cursor(&self) -> Option<&str> {
+        let paging = self.paging.as_ref()?;
+        paging.next.as_ref()?;
+        paging.cursors.as_ref()?.after.as_deref()
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Paging {
+    pub cursors: Option<Cursors>,
+    pub next: Option<String>,
+    pub previous: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Cursors {
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CreatedTemplate {
+    pub id: String,
+    pub status: Option<TemplateStatus>,
+    pub category: Option<TemplateCategory>,
+}
+
+fn string_or_first<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum StringOrList {
+        String(String),
+        List(Vec<String>),
+    }
+
+    match StringOrList::deserialize(deserializer)? {
+        StringOrList::String(example) => Ok(example),
+        StringOrList::List(examples) => Ok(examples.into_iter().next().unwrap_or_default()),
+    }
+}
+
+/// Names of the `{{1}}` / `{{first_name}}` placeholders of a template text,
+/// in order of first appearance and without duplicates
+pub(crate) fn placeholders(text: &str) -> Vec<&str> {
+    let mut placeholders = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        let after_start = &rest[start + 2..];
+        let Some(end) = after_start.find("}}") else {
+            break;
+        };
+        let name = after_start[..end].trim();
+        if !name.is_empty() && !placeholders.contains(&name) {
+            placeholders.push(name);
+        }
+        rest = &after_start[end + 2..];
+    }
+    placeholders
+}
+
+/// Text of a body component. Authentication templates created with this crate have
+/// no body text, WhatsApp sets it around the code.
+pub(crate) fn body_text(text: Option<&str>, category: TemplateCategory) -> Option<&str> {
+    match (text, category) {
+        (Some(text), _) => Some(text),
+        (None, TemplateCategory::Authentication) => Some("{{1}} is your verification code."),
+        (None, _) => None,
+    }
+}
@@ -1,8 +1,21 @@
 use serde::{Deserialize, Serialize};
 
-use super::component::Component;
+use super::{
+    component::{Component, ComponentSubType, ComponentType, Parameter, ParameterType},
+    template_definition::{
+        body_text, placeholders, HeaderFormat, ParameterFormat, TemplateButton, TemplateCategory,
+        TemplateComponent, TemplateDefinition,
+    },
+    validation::{check_count, check_length, Violation},
+};
 
 const DETERMINISTIC: &str = "deterministic";
+const MAX_NAME_LENGTH: usize = 512;
+const MAX_HEADER_TEXT_PARAMETER_LENGTH: usize = 60;
+const MAX_BODY_TEXT_PARAMETER_LENGTH: usize = 1024;
+const MAX_BODY_ONLY_TEXT_PARAMETER_LENGTH: usize = 32768;
+const MAX_BUTTON_INDEX: usize = 9;
+const MAX_CAROUSEL_CARDS: usize = 10;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Template {
@@ -31,6 +44,473 @@ impl Template {
             components: Some(components),
         }
     }
+
+    /// Authentication template sending a one-time password. The code is set both
+    /// in the body and in the copy code / one-tap autofill button (index 0).
+    /// The expiry warning is part of the template footer, see
+    /// [`TemplateDefinition::authentication`].
+    pub fn authentication(name: &str, language: &str, otp: &str) -> Self {
+        let components = vec![
+            Component::with_parameters(ComponentType::Body, vec![Parameter::from_text(otp)]),
+            Component::for_button(
+                ComponentType::Button,
+                ComponentSubType::Url,
+                vec![Parameter::from_text(otp)],
+                0,
+            ),
+        ];
+        Self::with_components(name, language, components)
+    }
+}
+
+impl Template {
+    pub(crate) fn components(&self) -> &[Component] {
+        self.components.as_deref().unwrap_or_default()
+    }
+
+    pub(crate) fn validate(&self, field: &str, violations: &mut Vec<Violation>) {
+        check_length(
+            &format!("{field}.name"),
+            &self.name,
+            MAX_NAME_LENGTH,
+            violations,
+        );
+
+        // Body parameters are limited to 1024 characters only when the template has other components
+        let body_only = self
+            .components()
+            .iter()
+            .all(|component| matches!(component.component_type, ComponentType::Body));
+        let max_body_text_length = if body_only {
+            MAX_BODY_ONLY_TEXT_PARAMETER_LENGTH
+        } else {
+            MAX_BODY_TEXT_PARAMETER_LENGTH
+        };
+
+        for (i, component) in self.components.iter().flatten().enumerate() {
+            let field = format!("{field}.components[{i}]");
+            let max_text_length = match component.component_type {
+                ComponentType::Header => Some(MAX_HEADER_TEXT_PARAMETER_LENGTH),
+                ComponentType::Body => Some(max_body_text_length),
+                ComponentType::Button | ComponentType::Carousel => None,
+            };
+
+            if let Some(max_text_length) = max_text_length {
+                for (j, parameter) in component.parameters.iter().flatten().enumerate() {
+                    if let Some(text) = &parameter.text {
+                        check_length(
+                            &format!("{field}.parameters[{j}].text"),
+                            text,
+                            max_text_length,
+                            violations,
+                        );
+                    }
+                }
+            }
+
+            if let Some(cards) = &component.cards {
+                check_count(
+                    &format!("{field}.cards"),
+                    cards.len(),
+                    MAX_CAROUSEL_CARDS,
+                    violations,
+                );
+            }
+
+            if let Some(index) = component.index {
+                if index < 0 || index as usize > MAX_BUTTON_INDEX {
+                    violations.push(Violation::OutOfRange {
+                        field: format!("{field}.index"),
+                        max: MAX_BUTTON_INDEX,
+                        actual: index.max(0) as usize,
+                    });
+                }
+            }
+        }
+    }
+}
+
+impl Template {
+    /// Checks the parameters of this template message against the definition of the
+    /// approved template: the parameters of every placeholder, their types, the header
+    /// format and the button indexes. An empty list means the message matches.
+    pub fn validate_against(&self, definition: &TemplateDefinition) -> Vec<Violation> {
+        let mut violations = Vec::new();
+        let format = definition
+            .parameter_format
+            .unwrap_or(ParameterFormat::Positional);
+        validate_components(
+            "template",
+            self.components(),
+            &definition.components,
+            definition.category,
+            format,
+            &mut violations,
+        );
+        violations
+    }
+}
+
+const TEXT_PARAMETER_TYPES: &[ParameterType] = &[
+    ParameterType::Text,
+    ParameterType::Currency,
+    ParameterType::DateTime,
+];
+
+fn validate_components(
+    field: &str,
+    components: &[Component],
+    definitions: &[TemplateComponent],
+    category: TemplateCategory,
+    format: ParameterFormat,
+    violations: &mut Vec<Violation>,
+) {
+    let mut header_definition = None;
+    let mut body_definition = None;
+    let mut button_definitions: &[TemplateButton] = &[];
+    let mut card_definitions = None;
+    for definition in definitions {
+        match definition {
+            TemplateComponent::Header { format, text, .. } => {
+                header_definition = Some((*format, text.as_deref().unwrap_or_default()))
+            }
+            TemplateComponent::Body { text, .. } => {
+                body_definition = body_text(text.as_deref(), category)
+            }
+            TemplateComponent::Buttons { buttons } => button_definitions = buttons,
+            TemplateComponent::Carousel { cards } => card_definitions = Some(cards),
+            TemplateComponent::Footer { .. } | TemplateComponent::Unknown => {}
+        }
+    }
+
+    let mut has_header = false;
+    let mut has_body = false;
+    let mut has_carousel = false;
+    let mut button_indexes = Vec::new();
+    for (i, component) in components.iter().enumerate() {
+        let field = format!("{field}.components[{i}]");
+        let parameters = component.parameters.as_deref().unwrap_or_default();
+        match component.component_type {
+            ComponentType::Header => {
+                has_header = true;
+                match header_definition {
+                    Some((HeaderFormat::Text, text)) => check_text_parameters(
+                        &field,
+                        parameters,
+                        text,
+                        format,
+                        &[ParameterType::Text],
+                        violations,
+                    ),
+                    // The parameters of a header format unknown to this crate are not checked
+                    Some((HeaderFormat::Unknown, _)) => {}
+                    Some((header_format, _)) => check_parameters(
+                        &field,
+                        parameters,
+                        1,
+                        &[media_parameter_type(header_format)],
+                        violations,
+                    ),
+                    None => violations.push(Violation::Unexpected { field }),
+                }
+            }
+            ComponentType::Body => {
+                has_body = true;
+                match body_definition {
+                    Some(text) => check_text_parameters(
+                        &field,
+                        parameters,
+                        text,
+                        format,
+                        TEXT_PARAMETER_TYPES,
+                        violations,
+                    ),
+                    None => violations.push(Violation::Unexpected { field }),
+                }
+            }
+            ComponentType::Button => {
+                if let Some(index) = check_button(
+                    &field,
+                    component,
+                    parameters,
+                    button_definitions,
+                    violations,
+                ) {
+                    button_indexes.push(index);
+                }
+            }
+            ComponentType::Carousel => {
+                has_carousel = true;
+                let Some(card_definitions) = card_definitions else {
+                    violations.push(Violation::Unexpected { field });
+                    continue;
+                };
+                for (j, card) in component.cards.iter().flatten().enumerate() {
+                    let field = format!("{field}.cards[{j}]");
+                    let card_definition = usize::try_from(card.card_index)
+                        .ok()
+                        .and_then(|index| card_definitions.get(index));
+                    match card_definition {
+                        Some(card_definition) => validate_components(
+                            &field,
+                            &card.components,
+                            &card_definition.components,
+                            category,
+                            format,
+                            violations,
+                        ),
+                        None => violations.push(Violation::OutOfRange {
+                            field: format!("{field}.card_index"),
+                            max: card_definitions.len().saturating_sub(1),
+                            actual: card.card_index.max(0) as usize,
+                        }),
+                    }
+                }
+            }
+        }
+    }
+
+    let header_needs_parameters = match header_definition {
+        Some((HeaderFormat::Text, text)) => !placeholders(text).is_empty(),
+        Some((HeaderFormat::Unknown, _)) => false,
+        Some(_) => true,
+        None => false,
+    };
+    if header_needs_parameters && !has_header {
+        violations.push(Violation::Missing {
+            field: format!("{field}.components.header"),
+        });
+    }
+    if body_definition.is_some_and(|text| !placeholders(text).is_empty()) && !has_body {
+        violations.push(Violation::Missing {
+            field: format!("{field}.components.body"),
+        });
+    }
+    for (index, button) in button_definitions.iter().enumerate() {
+        let required = button_expectation(button).is_some_and(|expectation| expectation.required);
+        if required && !button_indexes.contains(&index) {
+            violations.push(Violation::Missing {
+                field: format!("{field}.components.buttons[{index}]"),
+            });
+        }
+    }
+    if card_definitions.is_some() && !has_carousel {
+        violations.push(Violation::Missing {
+            field: format!("{field}.components.carousel"),
+        });
+    }
+}
+
+/// Checks a button component and returns the index of the button it fills
+fn check_button(
+    field: &str,
+    component: &Component,
+    parameters: &[Parameter],
+    button_definitions: &[TemplateButton],
+    violations: &mut Vec<Violation>,
+) -> Option<usize> {
+    let Some(index) = component.index else {
+        violations.push(Violation::Missing {
+            field: format!("{field}.index"),
+        });
+        return None;
+    };
+    let button = usize::try_from(index)
+        .ok()
+        .and_then(|i| button_definitions.get(i).map(|button| (i, button)));
+    let Some((index, button)) = button else {
+        if button_definitions.is_empty() {
+            violations.push(Violation::Unexpected {
+                field: field.into(),
+            });
+        } else {
+            violations.push(Violation::OutOfRange {
+                field: format!("{field}.index"),
+                max: button_definitions.len() - 1,
+                actual: index.max(0) as usize,
+            });
+        }
+        return None;
+    };
+
+    // The parameters of a button type unknown to this crate are not checked
+    if matches!(button, TemplateButton::Unknown) {
+        return Some(index);
+    }
+    let Some(expectation) = button_expectation(button) else {
+        // Buttons such as phone numbers take no parameters
+        violations.push(Violation::Unexpected {
+            field: field.into(),
+        });
+        return Some(index);
+    };
+    if component.sub_type.as_ref() != Some(&expectation.sub_type) {
+        violations.push(Violation::WrongType {
+            field: format!("{field}.sub_type"),
+            expected: expectation.sub_type.as_str().into(),
+            actual: component
+                .sub_type
+                .as_ref()
+                .map_or("none", ComponentSubType::as_str)
+                .into(),
+        });
+    }
+    check_parameters(
+        field,
+        parameters,
+        expectation.parameters,
+        expectation.parameter_types,
+        violations,
+    );
+    Some(index)
+}
+
+struct ButtonExpectation {
+    sub_type: ComponentSubType,
+    parameters: usize,
+    parameter_types: &'static [ParameterType],
+    /// Whether the message must fill this button for the template to be sent
+    required: bool,
+}
+
+fn button_expectation(button: &TemplateButton) -> Option<ButtonExpectation> {
+    let (sub_type, parameters, parameter_types, required) = match button {
+        TemplateButton::QuickReply { .. } => (
+            ComponentSubType::QuickReply,
+            1,
+            &[ParameterType::Payload][..],
+            false,
+        ),
+        TemplateButton::Url { url, .. } => {
+            let parameters = placeholders(url).len();
+            (
+                ComponentSubType::Url,
+                parameters,
+                &[ParameterType::Text][..],
+                parameters > 0,
+            )
+        }
+        // One-time passwords are sent as a url button, see Template::authentication
+        TemplateButton::Otp { .. } => (ComponentSubType::Url, 1, &[ParameterType::Text][..], true),
+        TemplateButton::CopyCode { .. } => (
+            ComponentSubType::CopyCode,
+            1,
+            &[ParameterType::CouponCode][..],
+            true,
+        ),
+        TemplateButton::Flow { .. } => (
+            ComponentSubType::Flow,
+            1,
+            &[ParameterType::Action][..],
+            false,
+        ),
+        TemplateButton::Catalog { .. } => (
+            ComponentSubType::Catalog,
+            1,
+            &[ParameterType::Action][..],
+            true,
+        ),
+        TemplateButton::Mpm { .. } => {
+            (ComponentSubType::Mpm, 1, &[ParameterType::Action][..], true)
+        }
+        TemplateButton::PhoneNumber { .. } | TemplateButton::Unknown => return None,
+    };
+    Some(ButtonExpectation {
+        sub_type,
+        parameters,
+        parameter_types,
+        required,
+    })
+}
+
+fn media_parameter_type(format: HeaderFormat) -> ParameterType {
+    match format {
+        HeaderFormat::Text | HeaderFormat::Unknown => ParameterType::Text,
+        HeaderFormat::Image => ParameterType::Image,
+        HeaderFormat::Video => ParameterType::Video,
+        HeaderFormat::Document => ParameterType::Document,
+        HeaderFormat::Location => ParameterType::Location,
+    }
+}
+
+/// Checks the parameters filling the placeholders of a header or body text
+fn check_text_parameters(
+    field: &str,
+    parameters: &[Parameter],
+    text: &str,
+    format: ParameterFormat,
+    parameter_types: &[ParameterType],
+    violations: &mut Vec<Violation>,
+) {
+    let placeholders = placeholders(text);
+    if format == ParameterFormat::Positional {
+        check_parameters(
+            field,
+            parameters,
+            placeholders.len(),
+            parameter_types,
+            violations,
+        );
+        return;
+    }
+
+    for (j, parameter) in parameters.iter().enumerate() {
+        let field = format!("{field}.parameters[{j}]");
+        match parameter.parameter_name.as_deref() {
+            Some(name) if placeholders.contains(&name) => {
+                check_parameter_type(&field, parameter, parameter_types, violations)
+            }
+            _ => violations.push(Violation::Unexpected { field }),
+        }
+    }
+    for name in placeholders {
+        if !parameters
+            .iter()
+            .any(|parameter| parameter.parameter_name.as_deref() == Some(name))
+        {
+            violations.push(Violation::Missing {
+                field: format!("{field}.parameters[{name}]"),
+            });
+        }
+    }
+}
+
+fn check_parameters(
+    field: &str,
+    parameters: &[Parameter],
+    expected: usize,
+    parameter_types: &[ParameterType],
+    violations: &mut Vec<Violation>,
+) {
+    for (j, parameter) in parameters.iter().enumerate() {
+        let field = format!("{field}.parameters[{j}]");
+        if j < expected {
+            check_parameter_type(&field, parameter, parameter_types, violations);
+        } else {
+            violations.push(Violation::Unexpected { field });
+        }
+    }
+    for j in parameters.len()..expected {
+        violations.push(Violation::Missing {
+            field: format!("{field}.parameters[{j}]"),
+        });
+    }
+}
+
+fn check_parameter_type(
+    field: &str,
+    parameter: &Parameter,
+    parameter_types: &[ParameterType],
+    violations: &mut Vec<Violation>,
+) {
+    if !parameter_types.contains(&parameter.parameter_type) {
+        let expected: Vec<&str> = parameter_types.iter().map(ParameterType::as_str).collect();
+        violations.push(Violation::WrongType {
+            field: format!("{field}.type"),
+            expected: expected.join(" or "),
+            actual: parameter.parameter_type.as_str().into(),
+        });
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
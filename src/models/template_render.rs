@@ -0,0 +1,356 @@
+use super::{
+    component::{Component, ComponentType, Parameter, ParameterType},
+    template_definition::{
+        body_text, HeaderFormat, TemplateButton, TemplateCategory, TemplateComponent,
+        TemplateDefinition,
+    },
+    template_message::Template,
+};
+
+/// A template message as the customer sees it, rendered locally from the template
+/// definition and the parameters of the message (e.g. for previews or audit logs)
+#[derive(Clone, Debug, PartialEq)]
+pub struct RenderedTemplate {
+    pub header: Option<RenderedHeader>,
+    pub body: Option<String>,
+    pub footer: Option<String>,
+    pub buttons: Vec<RenderedButton>,
+    pub cards: Vec<RenderedTemplate>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum RenderedHeader {
+    Text(String),
+    /// Image, video, document or location header, with the media link when it is known
+    Media {
+        format: HeaderFormat,
+        link: Option<String>,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RenderedButton {
+    pub text: String,
+    /// Url, phone number or code of the button
+    pub value: Option<String>,
+}
+
+impl Template {
+    /// Renders this template message with the texts of its definition. Placeholders
+    /// are replaced by the parameters of the message; currency and date_time
+    /// parameters are rendered with their fallback value. Placeholders without a
+    /// parameter are kept as is.
+    pub fn render(&self, definition: &TemplateDefinition) -> RenderedTemplate {
+        render_components(
+            self.components(),
+            &definition.components,
+            definition.category,
+        )
+    }
+}
+
+fn render_components(
+    components: &[Component],
+    definitions: &[TemplateComponent],
+    category: TemplateCategory,
+) -> RenderedTemplate {
+    let mut rendered = RenderedTemplate {
+        header: None,
+        body: None,
+        footer: None,
+        buttons: Vec::new(),
+        cards: Vec::new(),
+    };
+
+    for definition in definitions {
+        match definition {
+            TemplateComponent::Header { format, text, .. } => {
+                let parameters = component_parameters(components, |component| {
+                    matches!(component.component_type, ComponentType::Header)
+                });
+                rendered.header = Some(match format {
+                    HeaderFormat::Text => RenderedHeader::Text(substitute(
+                        text.as_deref().unwrap_or_default(),
+                        parameters,
+                    )),
+                    format => RenderedHeader::Media {
+                        format: *format,
+                        link: parameters.first().and_then(media_link),
+                    },
+                });
+            }
+            TemplateComponent::Body { text, .. } => {
+                let parameters = component_parameters(components, |component| {
+                    matches!(component.component_type, ComponentType::Body)
+                });
+                rendered.body =
+                    body_text(text.as_deref(), category).map(|text| substitute(text, parameters));
+            }
+            TemplateComponent::Footer {
+                text,
+                code_expiration_minutes,
+            } => {
+                rendered.footer = match (text, code_expiration_minutes) {
+                    (Some(text), _) => Some(text.clone()),
+                    (None, Some(minutes)) => {
+                        Some(format!("This code expires in {minutes} minutes."))
+                    }
+                    (None, None) => None,
+                };
+            }
+            TemplateComponent::Buttons { buttons } => {
+                for (i, button) in buttons.iter().enumerate() {
+                    if matches!(button, TemplateButton::Unknown) {
+                        continue;
+                    }
+                    let parameters = component_parameters(components, |component| {
+                        matches!(component.component_type, ComponentType::Button)
+                            && component.index == Some(i as i32)
+                    });
+                    rendered.buttons.push(render_button(button, parameters));
+                }
+            }
+            TemplateComponent::Carousel { cards } => {
+                let carousel = components
+                    .iter()
+                    .find(|component| matches!(component.component_type, ComponentType::Carousel))
+                    .and_then(|component| component.cards.as_deref())
+                    .unwrap_or_default();
+                for (i, card) in cards.iter().enumerate() {
+                    let card_components = carousel
+                        .iter()
+                        .find(|sent| sent.card_index == i as i32)
+                        .map(|sent| sent.components.as_slice())
+                        .unwrap_or_default();
+                    rendered.cards.push(render_components(
+                        card_components,
+                        &card.components,
+                        category,
+                    ));
+                }
+            }
+            TemplateComponent::Unknown => {}
+        }
+    }
+    rendered
+}
+
+fn render_button(button: &TemplateButton, parameters: &[Parameter]) -> RenderedButton {
+    let first_value = || parameters.first().and_then(parameter_value);
+    let (text, value) = match button {
+        TemplateButton::QuickReply { text }
+        | TemplateButton::Flow { text, .. }
+        | TemplateButton::Catalog { text }
+        | TemplateButton::Mpm { text } => (text.clone(), None),
+        TemplateButton::Url { text, url, .. } => (text.clone(), Some(substitute(url, parameters))),
+        TemplateButton::PhoneNumber { text, phone_number } => {
+            (text.clone(), Some(phone_number.clone()))
+        }
+        TemplateButton::CopyCode { .. } => ("Copy code".into(), first_value()),
+        TemplateButton::Otp { text, .. } => (
+            text.clone().unwrap_or_else(|| "Copy code".into()),
+            first_value(),
+        ),
+        TemplateButton::Unknown => ("Button".into(), None),
+    };
+    RenderedButton { text, value }
+}
+
+fn component_parameters(
+    components: &[Component],
+    predicate: impl Fn(&Component) -> bool,
+) -> &[Parameter] {
+    components
+        .iter()
+        .find(|component| predicate(component))
+        .and_then(|component| component.parameters.as_deref())
+        .unwrap_or_default()
+}
+
+/// Replaces `{{1}}` placeholders by position and `{{first_name}}` placeholders by name
+fn substitute(text: &str, parameters: &[Parameter]) -> String {
+    let mut rendered = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        let Some(end) = rest[start..].find("}}").map(|end| start + end + 2) else {
+            break;
+        };
+        rendered.push_str(&rest[..start]);
+        let name = rest[start + 2..end - 2].trim();
+        let parameter = match name.parse::<usize>() {
+            Ok(position) => position
+                .checked_sub(1)
+                .and_then(|index| parameters.get(index)),
+            Err(_) => parameters
+                .iter()
+                .find(|parameter| parameter.parameter_name.as_deref() == Some(name)),
+        };
+        match parameter.and_then(parameter_value) {
+            Some(value) => rendered.push_str(&value),
+            None => rendered.push_str(&rest[start..end]),
+        }
+        rest = &rest[end..];
+    }
+    rendered.push_str(rest);
+    rendered
+}
+
+fn parameter_value(parameter: &Parameter) -> Option<String> {
+    match parameter.parameter_type {
+        ParameterType::Text => parameter.text.clone(),
+        ParameterType::Currency => parameter
+            .currency
+            .as_ref()
+            .map(|currency| currency.fallback_value.clone()),
+        ParameterType::DateTime => parameter
+            .date_time
+            .as_ref()
+            .map(|date_time| date_time.fallback_value.clone()),
+        ParameterType::Payload => parameter.payload.clone(),
+        ParameterType::CouponCode => parameter.coupon_code.clone(),
+        ParameterType::Location => parameter.location.as_ref().map(|location| {
+            [location.name.as_deref(), location.address.as_deref()]
+                .into_iter()
+                .flatten()
+                .collect::<Vec<_>>()
+                .join(", ")
+        }),
+        ParameterType::Image | ParameterType::Video | ParameterType::Document => {
+            media_link(parameter)
+        }
+        ParameterType::Action => None,
+    }
+}
+
+fn media_link(parameter: &Parameter) -> Option<String> {
+    [&parameter.image, &parameter.video, &parameter.document]
+        .into_iter()
+        .flatten()
+        .find_map(|media| media.link.clone())
+}
+
+fn media_label(format: HeaderFormat) -> &'static str {
+    match format {
+        HeaderFormat::Text => "[Text]",
+        HeaderFormat::Image => "[Image]",
+        HeaderFormat::Video => "[Video]",
+        HeaderFormat::Document => "[Document]",
+        HeaderFormat::Location => "[Location]",
+        HeaderFormat::Unknown => "[Media]",
+    }
+}
+
+impl RenderedTemplate {
+    /// Plain text, with the header, body, footer and buttons separated by blank lines
+    pub fn to_text(&self) -> String {
+        let mut sections = Vec::new();
+        match &self.header {
+            Some(RenderedHeader::Text(text)) => sections.push(text.clone()),
+            Some(RenderedHeader::Media { format, link }) => sections.push(match link {
+                Some(link) => format!("{} {link}", media_label(*format)),
+                None => media_label(*format).into(),
+            }),
+            None => {}
+        }
+        sections.extend(self.body.clone());
+        sections.extend(self.footer.clone());
+        if !self.buttons.is_empty() {
+            let buttons: Vec<String> = self
+                .buttons
+                .iter()
+                .map(|button| match &button.value {
+                    Some(value) => format!("[{}] {value}", button.text),
+                    None => format!("[{}]", button.text),
+                })
+                .collect();
+            sections.push(buttons.join("\n"));
+        }
+        sections.extend(self.cards.iter().map(RenderedTemplate::to_text));
+        sections.join("\n\n")
+    }
+
+    /// Simple HTML card. Every value is escaped, and only http(s) links are rendered as links.
+    pub fn to_html(&self) -> String {
+        let mut html = String::from("<div class=\"whatsapp-template\">\n");
+        match &self.header {
+            Some(RenderedHeader::Text(text)) => html.push_str(&format!(
+                "<div class=\"whatsapp-template-header\"><strong>{}</strong></div>\n",
+                escape_html(text)
+            )),
+            Some(RenderedHeader::Media { format, link }) => {
+                let label = escape_html(media_label(*format));
+                let content = match link.as_deref().filter(|link| is_web_link(link)) {
+                    Some(link) if *format == HeaderFormat::Image => {
+                        format!("<img src=\"{}\" alt=\"{label}\">", escape_html(link))
+                    }
+                    Some(link) => format!("<a href=\"{}\">{label}</a>", escape_html(link)),
+                    None => label,
+                };
+                html.push_str(&format!(
+                    "<div class=\"whatsapp-template-header\">{content}</div>\n"
+                ));
+            }
+            None => {}
+        }
+        if let Some(body) = &self.body {
+            html.push_str(&format!(
+                "<div class=\"whatsapp-template-body\">{}</div>\n",
+                escape_html(body).replace('\n', "<br>")
+            ));
+        }
+        if let Some(footer) = &self.footer {
+            html.push_str(&format!(
+                "<div class=\"whatsapp-template-footer\"><small>{}</small></div>\n",
+                escape_html(footer)
+            ));
+        }
+        if !self.buttons.is_empty() {
+            html.push_str("<div class=\"whatsapp-template-buttons\">\n");
+            for button in &self.buttons {
+                let text = escape_html(&button.text);
+                let button = match button.value.as_deref() {
+                    Some(value) if is_web_link(value) => format!(
+                        "<a class=\"whatsapp-template-button\" href=\"{}\">{text}</a>",
+                        escape_html(value)
+                    ),
+                    Some(value) => format!(
+                        "<span class=\"whatsapp-template-button\">{text}: {}</span>",
+                        escape_html(value)
+                    ),
+                    None => format!("<span class=\"whatsapp-template-button\">{text}</span>"),
+                };
+                html.push_str(&button);
+                html.push('\n');
+            }
+            html.push_str("</div>\n");
+        }
+        if !self.cards.is_empty() {
+            html.push_str("<div class=\"whatsapp-template-carousel\">\n");
+            for card in &self.cards {
+                html.push_str(&card.to_html());
+            }
+            html.push_str("</div>\n");
+        }
+        html.push_str("</div>\n");
+        html
+    }
+}
+
+fn is_web_link(value: &str) -> bool {
+    value.starts_with("https://") || value.starts_with("http://")
+}
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -1,4 +1,16 @@
 use serde::{Deserialize, Serialize};
+use unicode_segmentation::UnicodeSegmentation;
+
+use super::validation::{check_length, Violation};
+
+pub(crate) const MAX_BODY_LENGTH: usize = 4096;
+const MARKUP_CHARACTERS: [char; 4] = ['*', '_', '~', '`'];
+const SPAN_DELIMITERS: [&str; 5] = ["```", "*", "_", "~", "`"];
+/// Inserted between a markup character of user-supplied text and the content it
+/// could enclose. A span delimiter must touch a non-blank character on its inner
+/// side, and this character counts as blank (see `is_blank`), so the markup
+/// character is shown as is instead of starting or ending a formatted span.
+const ZERO_WIDTH_SPACE: char = '\u{200B}';
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Text {
@@ -21,3 +33,404 @@ impl Text {
         }
     }
 }
+
+impl Text {
+    pub(crate) fn validate(&self, field: &str, violations: &mut Vec<Violation>) {
+        check_length(
+            &format!("{field}.body"),
+            &self.body,
+            MAX_BODY_LENGTH,
+            violations,
+        );
+    }
+}
+
+/// Builder of a text message body with WhatsApp formatting (bold, italic,
+/// strikethrough, monospace, lists and quotes).
+///
+/// Text given to the builder is treated as user-supplied: markup characters
+/// (`*`, `_`, `~`, `` ` ``) that could open or close a span, and line prefixes
+/// (`>`, `-`, `1.`), are neutralised with a zero-width space, so they can't break
+/// or inject formatting. Use `raw` for trusted markup.
+#[derive(Clone, Debug, Default)]
+pub struct FormattedText {
+    body: String,
+    /// End of the last span, when it is the end of the body
+    span_end: Option<usize>,
+}
+
+impl FormattedText {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn text(mut self, text: &str) -> Self {
+        self.push_escaped(text);
+        self
+    }
+
+    /// Appends trusted markup, without escaping
+    pub fn raw(mut self, markup: &str) -> Self {
+        self.body.push_str(markup);
+        self
+    }
+
+    pub fn bold(self, text: &str) -> Self {
+        self.span("*", text)
+    }
+
+    pub fn italic(self, text: &str) -> Self {
+        self.span("_", text)
+    }
+
+    pub fn strikethrough(self, text: &str) -> Self {
+        self.span("~", text)
+    }
+
+    pub fn monospace(self, text: &str) -> Self {
+        self.span("```", text)
+    }
+
+    pub fn inline_code(self, text: &str) -> Self {
+        self.span("`", text)
+    }
+
+    pub fn line_break(mut self) -> Self {
+        self.body.push('\n');
+        self
+    }
+
+    /// Bulleted list, one item per line
+    pub fn bulleted_list(mut self, items: &[&str]) -> Self {
+        self.start_block();
+        for item in items {
+            self.body.push_str("- ");
+            self.push_escaped(&item.replace('\n', " "));
+            self.body.push('\n');
+        }
+        self
+    }
+
+    /// Numbered list, one item per line, starting from 1
+    pub fn numbered_list(mut self, items: &[&str]) -> Self {
+        self.start_block();
+        for (i, item) in items.iter().enumerate() {
+            self.body.push_str(&format!("{}. ", i + 1));
+            self.push_escaped(&item.replace('\n', " "));
+            self.body.push('\n');
+        }
+        self
+    }
+
+    /// Block quote. Every line of `text` is quoted.
+    pub fn quote(mut self, text: &str) -> Self {
+        self.start_block();
+        for line in text.lines() {
+            self.body.push_str("> ");
+            self.push_escaped(line);
+            self.body.push('\n');
+        }
+        self
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.body
+    }
+
+    /// Text message with the formatted body. Link previews are enabled when the
+    /// body contains a link.
+    pub fn build(self) -> Text {
+        let body = self.body.trim_end_matches('\n');
+        if body.contains("https://") || body.contains("http://") {
+            Text::with_preview_url(body)
+        } else {
+            Text::new(body)
+        }
+    }
+
+    /// Only monospace blocks can span several lines, so other spans are
+    /// delimited line by line
+    fn span(mut self, delimiter: &str, text: &str) -> Self {
+        if delimiter == "```" {
+            self.push_span(delimiter, text);
+            return self;
+        }
+        for (i, line) in text.split('\n').enumerate() {
+            if i > 0 {
+                self.body.push('\n');
+            }
+            self.push_span(delimiter, line);
+        }
+        self
+    }
+
+    /// WhatsApp does not format spans starting or ending with whitespace, so the
+    /// surrounding whitespace is kept outside of the delimiters. Nor does it format
+    /// spans touching a word, so a zero-width space separates them from it.
+    fn push_span(&mut self, delimiter: &str, text: &str) {
+        let trimmed = text.trim();
+        if trimmed.is_empty() {
+            self.body.push_str(text);
+            return;
+        }
+        let start = text.len() - text.trim_start().len();
+        let end = start + trimmed.len();
+        self.body.push_str(&text[..start]);
+        if self
+            .body
+            .chars()
+            .next_back()
+            .is_some_and(char::is_alphanumeric)
+        {
+            self.body.push(ZERO_WIDTH_SPACE);
+        }
+        self.body.push_str(delimiter);
+        self.push_escaped_in_span(trimmed);
+        self.body.push_str(delimiter);
+        self.span_end = Some(self.body.len());
+        self.body.push_str(&text[end..]);
+    }
+
+    fn start_block(&mut self) {
+        if !self.body.is_empty() && !self.body.ends_with('\n') {
+            self.body.push('\n');
+        }
+    }
+
+    fn at_line_start(&self) -> bool {
+        self.body.is_empty() || self.body.ends_with('\n')
+    }
+
+    fn push_escaped(&mut self, text: &str) {
+        self.push_neutralised(text, false);
+    }
+
+    /// The first and last characters touch the delimiters of the enclosing span,
+    /// so they are not separated from them, which would break the span itself
+    fn push_escaped_in_span(&mut self, text: &str) {
+        self.push_neutralised(text, true);
+    }
+
+    fn push_neutralised(&mut self, text: &str, in_span: bool) {
+        let chars: Vec<char> = text.chars().collect();
+        let follows_span = self.span_end == Some(self.body.len());
+        if follows_span && chars.first().is_some_and(|c| c.is_alphanumeric()) {
+            self.body.push(ZERO_WIDTH_SPACE);
+        }
+        for (i, &c) in chars.iter().enumerate() {
+            if c != '\n' && self.at_line_start() {
+                let line: String = chars[i..].iter().take_while(|c| **c != '\n').collect();
+                if starts_with_block_markup(&line) {
+                    self.body.push(ZERO_WIDTH_SPACE);
+                }
+            }
+            if !MARKUP_CHARACTERS.contains(&c) {
+                self.body.push(c);
+                continue;
+            }
+
+            // The character that follows is unknown at the end of the text,
+            // so it is assumed to allow a span
+            let before = self.body.chars().next_back();
+            let after = chars.get(i + 1).copied();
+            let could_open =
+                !before.is_some_and(char::is_alphanumeric) && !after.is_some_and(is_blank);
+            let could_close =
+                before.is_some_and(|c| !is_blank(c)) && !after.is_some_and(char::is_alphanumeric);
+            if could_close && !(in_span && i == 0) {
+                self.body.push(ZERO_WIDTH_SPACE);
+            }
+            self.body.push(c);
+            if could_open && !(in_span && i + 1 == chars.len()) {
+                self.body.push(ZERO_WIDTH_SPACE);
+            }
+        }
+    }
+}
+
+impl From<FormattedText> for Text {
+    fn from(formatted_text: FormattedText) -> Self {
+        formatted_text.build()
+    }
+}
+
+/// Whether a line would be rendered as a quote or a list item
+fn starts_with_block_markup(line: &str) -> bool {
+    if line.starts_with('>') || line.starts_with("- ") || line.starts_with("* ") {
+        return true;
+    }
+    let digits = line.chars().take_while(char::is_ascii_digit).count();
+    digits > 0 && line[digits..].starts_with(". ")
+}
+
+/// Splits a text body into chunks of at most `max` characters. Chunks end on a
+/// paragraph, sentence or word boundary when there is one in the second half of
+/// the chunk, never inside a grapheme cluster, and before a formatting span
+/// (e.g. `*bold*`) that would otherwise be cut in two. A span longer than `max`
+/// can't be kept whole, so it is cut at the last whitespace before the limit.
+pub(crate) fn split_body(body: &str, max: usize) -> Vec<&str> {
+    let mut chunks = Vec::new();
+    let mut rest = body.trim();
+    while rest.chars().count() > max {
+        let split = split_point(rest, max);
+        let chunk = rest[..split].trim_end();
+        if !chunk.is_empty() {
+            chunks.push(chunk);
+        }
+        rest = rest[split..].trim_start();
+    }
+    if !rest.is_empty() {
+        chunks.push(rest);
+    }
+    chunks
+}
+
+fn split_point(text: &str, max: usize) -> usize {
+    // Longest prefix of whole grapheme clusters with at most `max` characters
+    let mut limit = 0;
+    let mut count = 0;
+    for (index, grapheme) in text.grapheme_indices(true) {
+        count += grapheme.chars().count();
+        if count > max {
+            break;
+        }
+        limit = index + grapheme.len();
+    }
+    if limit == 0 {
+        // A single grapheme cluster longer than `max`, it can only be cut
+        limit = text.char_indices().nth(max).map_or(text.len(), |(i, _)| i);
+    }
+
+    let window = &text[..limit];
+    let min = window
+        .char_indices()
+        .nth(max / 2)
+        .map_or(window.len(), |(i, _)| i);
+    let paragraph = window.rfind("\n\n").map(|i| i + 2);
+    let sentence = || {
+        window
+            .split_sentence_bound_indices()
+            .map(|(i, _)| i)
+            .filter(|i| *i > 0)
+            .last()
+    };
+    let word = || {
+        window
+            .char_indices()
+            .rev()
+            .find(|(_, c)| c.is_whitespace())
+            .map(|(i, c)| i + c.len_utf8())
+    };
+    let mut split = paragraph
+        .filter(|i| *i >= min)
+        .or_else(|| sentence().filter(|i| *i >= min))
+        .or_else(|| word().filter(|i| *i >= min))
+        .unwrap_or(limit);
+
+    if let Some((start, end)) = open_span(text, split) {
+        if text[start..end].chars().count() > max {
+            split = word().unwrap_or(limit);
+        } else if start > 0 {
+            split = start;
+        }
+    }
+    split
+}
+
+/// Start and end of the formatting span that the split point would cut in two, if any
+fn open_span(text: &str, split: usize) -> Option<(usize, usize)> {
+    let mut position = 0;
+    while position < split {
+        let rest = &text[position..];
+        let opening = SPAN_DELIMITERS.iter().find(|delimiter| {
+            rest.starts_with(**delimiter) && opens_span(text, position, delimiter)
+        });
+        let Some(delimiter) = opening else {
+            position += rest.chars().next().map_or(1, char::len_utf8);
+            continue;
+        };
+        let content_start = position + delimiter.len();
+        match closing_delimiter(text, content_start, delimiter) {
+            Some(end) if split < end => return Some((position, end)),
+            Some(end) => position = end,
+            None => position = content_start,
+        }
+    }
+    None
+}
+
+/// Whitespace, or the zero-width space used to neutralise markup characters
+fn is_blank(c: char) -> bool {
+    c.is_whitespace() || c == ZERO_WIDTH_SPACE
+}
+
+/// Whether the delimiter at `position` starts a span: preceded by the start of the
+/// text or a non-alphanumeric character, and followed by a non-blank character
+fn opens_span(text: &str, position: usize, delimiter: &str) -> bool {
+    let before = text[..position].chars().next_back();
+    let after = text[position + delimiter.len()..].chars().next();
+    !before.is_some_and(char::is_alphanumeric) && after.is_some_and(|c| !is_blank(c))
+}
+
+/// End of the delimiter closing a span whose content starts at `content_start`.
+/// Only monospace blocks can span several lines.
+fn closing_delimiter(text: &str, content_start: usize, delimiter: &str) -> Option<usize> {
+    let content = &text[content_start..];
+    let searched = if delimiter == "```" {
+        content
+    } else {
+        content.split('\n').next().unwrap_or_default()
+    };
+    searched.match_indices(delimiter).find_map(|(i, _)| {
+        let before = content[..i].chars().next_back();
+        let after = content[i + delimiter.len()..].chars().next();
+        let closes =
+            before.is_some_and(|c| !is_blank(c)) && !after.is_some_and(char::is_alphanumeric);
+        closes.then_some(content_start + i + delimiter.len())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Every formatted span of a body, as (start, end) byte ranges
+    fn spans(text: &str) -> Vec<(usize, usize)> {
+        let mut spans = Vec::new();
+        let mut position = 0;
+        while position < text.len() {
+            let rest = &text[position..];
+            let span = SPAN_DELIMITERS.iter().find_map(|delimiter| {
+                if !rest.starts_with(delimiter) || !opens_span(text, position, delimiter) {
+                    return None;
+                }
+                closing_delimiter(text, position + delimiter.len(), delimiter)
+            });
+            match span {
+                Some(end) => {
+                    spans.push((position, end));
+                    position = end;
+                }
+                None => position += rest.chars().next().map_or(1, char::len_utf8),
+            }
+        }
+        spans
+    }
+
+    #[test]
+    fn escaping_follows_the_span_rules() {
+        for text in [
+            "price is *not* final",
+            "**",
+            "_x_ ~y~ `z` ```w```",
+            "*bold*_italic_",
+        ] {
+            let body = FormattedText::new().text(text).body;
+            assert_eq!(spans(&body), vec![], "{body:?}");
+        }
+        for text in ["snake_case_name", "a*b *c* d", "* note", "x *", "```"] {
+            let body = FormattedText::new().bold(text).body;
+            assert_eq!(spans(&body), vec![(0, body.len())], "{body:?}");
+        }
+    }
+}
@@ -1,9 +1,95 @@
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+use tokio::io::AsyncRead;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct UploadSuccess {
     pub h: String,
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct UploadParams {
     pub file_name: String,
     pub file_length: String,
     pub file_type: String,
 }
+
+impl UploadParams {
+    pub fn new(file_name: &str, file_length: usize, file_type: &str) -> Self {
+        Self {
+            file_name: file_name.into(),
+            file_length: file_length.to_string(),
+            file_type: file_type.into(),
+        }
+    }
+}
+
+/// Session created with the Resumable Upload API (`/{app_id}/uploads`)
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct UploadSession {
+    pub id: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct UploadSessionStatus {
+    pub id: String,
+    pub file_offset: u64,
+}
+
+/// Response of uploading media to `/{phone_number_id}/media`.
+/// The id can be used to send media messages, e.g. with `Image::for_id`
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct UploadMediaResponse {
+    pub id: String,
+}
+
+/// Content of a media file to upload
+pub enum MediaSource {
+    Bytes(Vec<u8>),
+    Path(PathBuf),
+    Reader(Box<dyn AsyncRead + Send + Sync + Unpin>),
+}
+
+impl MediaSource {
+    pub fn from_reader<R>(reader: R) -> Self
+    where
+        R: AsyncRead + Send + Sync + Unpin + 'static,
+    {
+        Self::Reader(Box::new(reader))
+    }
+}
+
+impl std::fmt::Debug for MediaSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MediaSource::Bytes(bytes) => write!(f, "Bytes({} bytes)", bytes.len()),
+            MediaSource::Path(path) => f.debug_tuple("Path").field(path).finish(),
+            MediaSource::Reader(_) => f.write_str("Reader"),
+        }
+    }
+}
+
+impl From<Vec<u8>> for MediaSource {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self::Bytes(bytes)
+    }
+}
+
+impl From<&[u8]> for MediaSource {
+    fn from(bytes: &[u8]) -> Self {
+        Self::Bytes(bytes.to_vec())
+    }
+}
+
+impl From<PathBuf> for MediaSource {
+    fn from(path: PathBuf) -> Self {
+        Self::Path(path)
+    }
+}
+
+impl From<&std::path::Path> for MediaSource {
+    fn from(path: &std::path::Path) -> Self {
+        Self::Path(path.to_path_buf())
+    }
+}
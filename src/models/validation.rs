@@ -0,0 +1,110 @@
+/// A Cloud API limit that a message does not respect.
+/// `field` is the path of the offending value in the message payload,
+/// e.g. `interactive.action.buttons[0].reply.title`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Violation {
+    /// Text longer than `max` characters (counted as Unicode scalar values)
+    TooLong {
+        field: String,
+        max: usize,
+        actual: usize,
+    },
+    TooMany {
+        field: String,
+        max: usize,
+        actual: usize,
+    },
+    Empty {
+        field: String,
+    },
+    OutOfRange {
+        field: String,
+        max: usize,
+        actual: usize,
+    },
+    /// Value required by the template definition, e.g. a parameter of a `{{n}}` placeholder
+    Missing {
+        field: String,
+    },
+    /// Value that is not part of the template definition
+    Unexpected {
+        field: String,
+    },
+    WrongType {
+        field: String,
+        expected: String,
+        actual: String,
+    },
+}
+
+impl Violation {
+    pub fn field(&self) -> &str {
+        match self {
+            Violation::TooLong { field, .. }
+            | Violation::TooMany { field, .. }
+            | Violation::Empty { field }
+            | Violation::OutOfRange { field, .. }
+            | Violation::Missing { field }
+            | Violation::Unexpected { field }
+            | Violation::WrongType { field, .. } => field,
+        }
+    }
+}
+
+impl std::fmt::Display for Violation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Violation::TooLong { field, max, actual } => write!(
+                f,
+                "{field} is {actual} characters long, at most {max} are allowed"
+            ),
+            Violation::TooMany { field, max, actual } => {
+                write!(f, "{field} has {actual} items, at most {max} are allowed")
+            }
+            Violation::Empty { field } => write!(f, "{field} must not be empty"),
+            Violation::OutOfRange { field, max, actual } => {
+                write!(f, "{field} is {actual}, at most {max} is allowed")
+            }
+            Violation::Missing { field } => {
+                write!(f, "{field} is required by the template definition")
+            }
+            Violation::Unexpected { field } => {
+                write!(f, "{field} is not part of the template definition")
+            }
+            Violation::WrongType {
+                field,
+                expected,
+                actual,
+            } => write!(f, "{field} is {actual}, {expected} is expected"),
+        }
+    }
+}
+
+pub(crate) fn check_length(field: &str, value: &str, max: usize, violations: &mut Vec<Violation>) {
+    let actual = value.chars().count();
+    if actual == 0 {
+        violations.push(Violation::Empty {
+            field: field.into(),
+        });
+    } else if actual > max {
+        violations.push(Violation::TooLong {
+            field: field.into(),
+            max,
+            actual,
+        });
+    }
+}
+
+pub(crate) fn check_count(field: &str, actual: usize, max: usize, violations: &mut Vec<Violation>) {
+    if actual == 0 {
+        violations.push(Violation::Empty {
+            field: field.into(),
+        });
+    } else if actual > max {
+        violations.push(Violation::TooMany {
+            field: field.into(),
+            max,
+            actual,
+        });
+    }
+}
@@ -74,6 +74,7 @@ pub struct NotificationMessage {
     pub image: Option<Image>,
     pub interactive: Option<Interactive>,
     pub order: Option<Order>,
+    pub reaction: Option<Reaction>,
     pub sticker: Option<Sticker>,
     pub system: Option<System>,
     pub video: Option<Video>,
@@ -112,6 +113,7 @@ pub enum NotificationMessageType {
     Image,
     Interactive,
     Order,
+    Reaction,
     Sticker,
     System,
     Unknown,
@@ -151,8 +153,26 @@ pub struct Image {
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct Interactive {
+    #[serde(rename = "type")]
+    pub interactive_type: Option<String>,
     pub button_reply: Option<ButtonReply>,
     pub list_reply: Option<ListReply>,
+    pub nfm_reply: Option<NfmReply>,
+}
+
+/// Reply to a WhatsApp Flows message (`nfm_reply` interactive type)
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct NfmReply {
+    pub name: Option<String>,
+    pub body: Option<String>,
+    /// Data submitted by the flow, as a JSON encoded string
+    pub response_json: String,
+}
+
+impl NfmReply {
+    pub fn response(&self) -> Result<serde_json::Value, serde_json::Error> {
+        serde_json::from_str(&self.response_json)
+    }
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -183,6 +203,13 @@ pub struct ProductItem {
     pub currency: String,
 }
 
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct Reaction {
+    pub message_id: String,
+    /// Absent or empty when the user removed their reaction
+    pub emoji: Option<String>,
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct Sticker {
     pub mime_type: String,
@@ -0,0 +1,123 @@
+use std::time::Duration;
+
+use reqwest::Method;
+
+use crate::WhatsappError;
+
+/// Controls how [`WhatsappClient`](crate::WhatsappClient) retries requests that
+/// failed with a transient error.
+///
+/// Requests that are not idempotent (e.g. sending a message) are only retried
+/// when the request is known not to have been processed: the connection could
+/// not be established, or the Graph API rejected it because of throttling.
+///
+/// When the server sends a Retry-After header, in seconds or as an HTTP date,
+/// it replaces the backoff delay, up to `max_delay`.
+#[derive(Clone, Debug)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    base_delay: Duration,
+    max_delay: Duration,
+    jitter: f64,
+    retry_throttling: bool,
+    retry_server_errors: bool,
+    retry_network_errors: bool,
+}
+
+impl RetryPolicy {
+    /// Policy making at most `max_attempts` attempts (including the first one),
+    /// starting with a 500ms delay that doubles on every retry, up to 30s.
+    pub fn new(max_attempts: u32) -> Self {
+        Self {
+            max_attempts: max_attempts.max(1),
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            jitter: 0.5,
+            retry_throttling: true,
+            retry_server_errors: true,
+            retry_network_errors: true,
+        }
+    }
+
+    /// Policy that never retries. This is the default for a new client.
+    pub fn none() -> Self {
+        Self::new(1)
+    }
+
+    pub fn max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    pub fn base_delay(mut self, base_delay: Duration) -> Self {
+        self.base_delay = base_delay;
+        self
+    }
+
+    /// Longest wait between attempts, also capping the Retry-After asked by the server
+    pub fn max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    /// Fraction (between 0 and 1) of each delay that is randomized
+    pub fn jitter(mut self, jitter: f64) -> Self {
+        self.jitter = jitter.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Retry throttling errors (codes 4, 80007, 130429, 131048, 131056 and http 429)
+    pub fn retry_throttling(mut self, retry: bool) -> Self {
+        self.retry_throttling = retry;
+        self
+    }
+
+    /// Retry 5xx responses and Graph API errors documented as temporary
+    pub fn retry_server_errors(mut self, retry: bool) -> Self {
+        self.retry_server_errors = retry;
+        self
+    }
+
+    /// Retry connection failures, resets and timeouts
+    pub fn retry_network_errors(mut self, retry: bool) -> Self {
+        self.retry_network_errors = retry;
+        self
+    }
+
+    pub(crate) fn should_retry(&self, error: &WhatsappError, method: &Method) -> bool {
+        let idempotent = matches!(
+            *method,
+            Method::GET | Method::HEAD | Method::PUT | Method::DELETE | Method::OPTIONS
+        );
+
+        match error {
+            WhatsappError::ReqwestError(e) if e.is_connect() => self.retry_network_errors,
+            WhatsappError::ReqwestError(e) if e.is_timeout() || e.is_request() => {
+                self.retry_network_errors && idempotent
+            }
+            WhatsappError::Api(e) if e.is_rate_limited() => self.retry_throttling,
+            WhatsappError::Api(e) if e.is_retryable() => self.retry_server_errors && idempotent,
+            _ => false,
+        }
+    }
+
+    /// Delay before the attempt following `attempt` (starting at 1)
+    pub(crate) fn delay(&self, attempt: u32, retry_after: Option<Duration>) -> Duration {
+        // A server asking to wait longer than max_delay must not block the call for that long
+        if let Some(retry_after) = retry_after {
+            return retry_after.min(self.max_delay);
+        }
+
+        let exponent = attempt.saturating_sub(1).min(16);
+        let delay = self
+            .base_delay
+            .saturating_mul(1 << exponent)
+            .min(self.max_delay);
+        delay.mul_f64(1.0 - self.jitter * fastrand::f64())
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::none()
+    }
+}
@@ -1,51 +1,75 @@
+use std::time::{Duration, SystemTime};
+
 use reqwest::{
-    header::{self, AUTHORIZATION},
-    Request, StatusCode,
+    header::{HeaderValue, AUTHORIZATION, RETRY_AFTER},
+    multipart::{Form, Part},
+    Body, Request, Response, StatusCode,
 };
+use sha2::{Digest, Sha256};
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+use tokio_util::io::ReaderStream;
 
 use crate::{
+    error::ApiErrorResponse,
     models::{
         BusinessProfileData, BusinessProfileResponse, CodeMethod, CodeRequestParams,
         CodeVerifyParams, ConnectCatalogToWhatsappBusiness, CreateProductCatalogRequest,
-        EditItemProduct, ItemProduct, MediaResponse, Message, MessageResponse, MessageStatus,
-        MessageStatusResponse, PhoneNumberResponse, ProductCatalog, Success,
-        UpdateBusinessProfileResponse,
+        CreatedTemplate, EditItemProduct, ItemProduct, MediaResponse, MediaSource, Message,
+        MessageResponse, MessageStatus, MessageStatusResponse, PhoneNumberResponse, ProductCatalog,
+        Success, TemplateDefinition, TemplateEdit, TemplateFilters, TemplateList,
+        UpdateBusinessProfileResponse, UploadMediaResponse, UploadParams, UploadSession,
+        UploadSessionStatus, UploadSuccess,
     },
-    WhatsappError,
+    MediaVerificationError, RetryPolicy, WhatsappClientBuilder, WhatsappError, WHATSAPP,
 };
 
-const FACEBOOK_GRAPH_API_BASE_URL: &str = "https://graph.facebook.com";
+pub(crate) const FACEBOOK_GRAPH_API_BASE_URL: &str = "https://graph.facebook.com";
+pub(crate) const FACEBOOK_GRAPH_API_VERSION: &str = "v20.0";
+const MAX_UPLOAD_RESUMES: u32 = 3;
 
 #[derive(Clone, Debug)]
 pub struct WhatsappClient {
+    base_url: String,
     version: String,
     access_token: String,
     client: reqwest::Client,
+    retry_policy: RetryPolicy,
+    validate_messages: bool,
 }
 
 impl WhatsappClient {
     pub fn new(access_token: &str) -> Self {
-        let auth_token: String = access_token.into();
-
-        let mut headers = header::HeaderMap::new();
-        headers.insert(
-            AUTHORIZATION,
-            header::HeaderValue::from_str(&format!("Bearer {}", auth_token.as_str()))
-                .expect("Error: could not parse auth token into a valid request header."),
-        );
-
-        let client = reqwest::Client::builder()
-            .default_headers(headers)
+        Self::builder(access_token)
             .build()
-            .expect("Error: could not initialize PedidosYa API client. Please try again!");
+            .expect("Error: could not initialize Whatsapp API client. Please try again!")
+    }
 
+    pub fn builder(access_token: &str) -> WhatsappClientBuilder {
+        WhatsappClientBuilder::new(access_token)
+    }
+
+    pub(crate) fn from_parts(
+        base_url: String,
+        version: String,
+        access_token: String,
+        client: reqwest::Client,
+        retry_policy: RetryPolicy,
+        validate_messages: bool,
+    ) -> Self {
         Self {
-            version: "v20.0".into(),
-            access_token: access_token.into(),
+            base_url,
+            version,
+            access_token,
             client,
+            retry_policy,
+            validate_messages,
         }
     }
 
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
     pub fn version(&mut self) -> &str {
         &self.version
     }
@@ -54,6 +78,20 @@ impl WhatsappClient {
         self.version = version.into();
     }
 
+    pub fn retry_policy(&self) -> &RetryPolicy {
+        &self.retry_policy
+    }
+
+    pub fn set_retry_policy(&mut self, retry_policy: RetryPolicy) {
+        self.retry_policy = retry_policy;
+    }
+
+    /// When enabled, send_message checks the message with Message::validate and
+    /// returns a ValidationError instead of sending an invalid message
+    pub fn set_validate_messages(&mut self, validate_messages: bool) {
+        self.validate_messages = validate_messages;
+    }
+
     pub fn set_access_token(&mut self, access_token: &str) {
         self.access_token = access_token.into();
     }
@@ -68,6 +106,13 @@ impl WhatsappClient {
         message: &Message,
     ) -> Result<MessageResponse, WhatsappError> {
         //http_client::post(&self.messages_api_url(), &self.access_token, message).await
+        if self.validate_messages {
+            let violations = message.validate();
+            if !violations.is_empty() {
+                return Err(WhatsappError::ValidationError(violations));
+            }
+        }
+
         let url = self.messages_api_url(&phone_number_id);
         let req = self
             .client
@@ -78,6 +123,21 @@ impl WhatsappClient {
         self.send_request(req).await
     }
 
+    /// Sends messages one after the other, each once the previous one was accepted,
+    /// e.g. the parts of Message::split_text. Stops at the first error, so the
+    /// following messages are not sent.
+    pub async fn send_messages_in_order(
+        &self,
+        phone_number_id: String,
+        messages: &[Message],
+    ) -> Result<Vec<MessageResponse>, WhatsappError> {
+        let mut responses = Vec::with_capacity(messages.len());
+        for message in messages {
+            responses.push(self.send_message(phone_number_id.clone(), message).await?);
+        }
+        Ok(responses)
+    }
+
     pub async fn request_code(
         &self,
         phone_number_id: String,
@@ -89,7 +149,7 @@ impl WhatsappClient {
             language: language.into(),
         };
 
-        let url = self.messages_api_url(&phone_number_id);
+        let url = self.request_code_api_url(&phone_number_id);
         let req = self
             .client
             .request(reqwest::Method::POST, url)
@@ -131,12 +191,186 @@ impl WhatsappClient {
         self.send_request(req).await
     }
 
-    pub async fn get_media(&self, media_id: &str) -> Result<MediaResponse, WhatsappError> {
+    /// Retrieves the url and metadata of a media. When `phone_number_id` is given,
+    /// the Graph API checks that the media belongs to that phone number.
+    pub async fn get_media(
+        &self,
+        media_id: &str,
+        phone_number_id: Option<&str>,
+    ) -> Result<MediaResponse, WhatsappError> {
+        let url = self.media_api_url(media_id);
+        let mut req = self.client.get(url);
+        if let Some(phone_number_id) = phone_number_id {
+            req = req.query(&[("phone_number_id", phone_number_id)]);
+        }
+        self.send_request(req.build()?).await
+    }
+
+    /// Deletes an uploaded media. When `phone_number_id` is given, the Graph API
+    /// checks that the media belongs to that phone number.
+    pub async fn delete_media(
+        &self,
+        media_id: &str,
+        phone_number_id: Option<&str>,
+    ) -> Result<Success, WhatsappError> {
         let url = self.media_api_url(media_id);
+        let mut req = self.client.delete(url);
+        if let Some(phone_number_id) = phone_number_id {
+            req = req.query(&[("phone_number_id", phone_number_id)]);
+        }
+        self.send_request(req.build()?).await
+    }
+
+    /// Starts a Resumable Upload API session, used to upload template header
+    /// samples and profile pictures
+    pub async fn create_upload_session(
+        &self,
+        app_id: &str,
+        params: &UploadParams,
+    ) -> Result<UploadSession, WhatsappError> {
+        let url = self.upload_session_url(app_id);
+        let req = self.client.post(url).query(params).build()?;
+
+        self.send_request(req).await
+    }
+
+    /// Returns the offset from which an interrupted upload should be resumed
+    pub async fn get_upload_session(
+        &self,
+        upload_session_id: &str,
+    ) -> Result<UploadSessionStatus, WhatsappError> {
+        let url = self.media_api_url(upload_session_id);
         let req = self.client.get(url).build()?;
+        let req = self.with_oauth_header(req)?;
+
+        self.send_request(req).await
+    }
+
+    /// Uploads `bytes` to an upload session, starting at `file_offset`
+    pub async fn upload_file_chunk(
+        &self,
+        upload_session_id: &str,
+        file_offset: u64,
+        bytes: Vec<u8>,
+    ) -> Result<UploadSuccess, WhatsappError> {
+        let url = self.media_api_url(upload_session_id);
+        let req = self
+            .client
+            .post(url)
+            .header("file_offset", file_offset)
+            .body(bytes)
+            .build()?;
+        let req = self.with_oauth_header(req)?;
+
         self.send_request(req).await
     }
 
+    /// Uploads a file with the Resumable Upload API, resuming from the offset
+    /// reported by the Graph API when an upload attempt fails.
+    /// The returned handle `h` can be used in template examples or as
+    /// `profile_picture_handle` of the business profile.
+    pub async fn resumable_upload(
+        &self,
+        app_id: &str,
+        file_name: &str,
+        file_type: &str,
+        bytes: Vec<u8>,
+    ) -> Result<UploadSuccess, WhatsappError> {
+        let params = UploadParams::new(file_name, bytes.len(), file_type);
+        let session = self.create_upload_session(app_id, &params).await?;
+
+        let mut file_offset = 0;
+        let mut resumes = 0;
+        loop {
+            let chunk = bytes[file_offset as usize..].to_vec();
+            let error = match self
+                .upload_file_chunk(&session.id, file_offset, chunk)
+                .await
+            {
+                Err(error) if resumes < MAX_UPLOAD_RESUMES => error,
+                result => return result,
+            };
+
+            resumes += 1;
+            file_offset = self.get_upload_session(&session.id).await?.file_offset;
+            if file_offset > bytes.len() as u64 {
+                return Err(error);
+            }
+            log::warn!(
+                "Upload to session {} failed: {error}. Resuming from offset {file_offset}",
+                session.id
+            );
+        }
+    }
+
+    /// Downloads the media with the given id, verifying its size and sha256 digest
+    pub async fn download_media(&self, media_id: &str) -> Result<Vec<u8>, WhatsappError> {
+        let mut bytes = Vec::new();
+        self.download_media_to(media_id, &mut bytes).await?;
+        Ok(bytes)
+    }
+
+    /// Streams the media with the given id into `writer`, verifying its size and
+    /// sha256 digest once complete. The download stops as soon as more bytes than
+    /// announced are received. On a verification error, part of the content may
+    /// already have been written and should be discarded by the caller.
+    pub async fn download_media_to<W>(
+        &self,
+        media_id: &str,
+        writer: &mut W,
+    ) -> Result<MediaResponse, WhatsappError>
+    where
+        W: AsyncWrite + Unpin,
+    {
+        let media = self.get_media(media_id, None).await?;
+
+        let mut req = self.client.get(&media.url).build()?;
+        req.headers_mut()
+            .insert(AUTHORIZATION, self.authorization_header()?);
+        let mut response = self.client.execute(req).await?;
+        if response.status() != StatusCode::OK {
+            return Err(response_error(response).await);
+        }
+
+        let expected_size = media.file_size as u64;
+        let mut hasher = Sha256::new();
+        let mut file_size: u64 = 0;
+        while let Some(chunk) = response.chunk().await? {
+            file_size += chunk.len() as u64;
+            if file_size > expected_size {
+                break;
+            }
+            hasher.update(&chunk);
+            writer.write_all(&chunk).await?;
+        }
+        writer.flush().await?;
+
+        if file_size != expected_size {
+            return Err(WhatsappError::MediaVerificationError(
+                MediaVerificationError::FileSizeMismatch {
+                    expected: expected_size,
+                    actual: file_size,
+                },
+            ));
+        }
+
+        let digest: String = hasher
+            .finalize()
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect();
+        if !digest.eq_ignore_ascii_case(&media.sha256) {
+            return Err(WhatsappError::MediaVerificationError(
+                MediaVerificationError::Sha256Mismatch {
+                    expected: media.sha256,
+                    actual: digest,
+                },
+            ));
+        }
+
+        Ok(media)
+    }
+
     pub async fn get_business_profile(
         &self,
         phone_number_id: String,
@@ -161,7 +395,35 @@ impl WhatsappClient {
         self.send_request(req).await
     }
 
-    pub async fn start_upload_img() {}
+    pub async fn upload_media(
+        &self,
+        phone_number_id: String,
+        media: impl Into<MediaSource>,
+        mime_type: &str,
+        filename: &str,
+    ) -> Result<UploadMediaResponse, WhatsappError> {
+        let file = match media.into() {
+            MediaSource::Bytes(bytes) => Part::bytes(bytes),
+            MediaSource::Path(path) => {
+                let file = tokio::fs::File::open(&path).await?;
+                Part::stream(Body::wrap_stream(ReaderStream::new(file)))
+            }
+            MediaSource::Reader(reader) => {
+                Part::stream(Body::wrap_stream(ReaderStream::new(reader)))
+            }
+        };
+        let file = file.file_name(filename.to_string()).mime_str(mime_type)?;
+
+        let form = Form::new()
+            .text("messaging_product", WHATSAPP)
+            .text("type", mime_type.to_string())
+            .part("file", file);
+
+        let url = self.upload_media_api_url(&phone_number_id);
+        let req = self.client.post(url).multipart(form).build()?;
+
+        self.send_request(req).await
+    }
 
     pub async fn create_product_catalog(
         &self,
@@ -232,6 +494,97 @@ impl WhatsappClient {
         self.send_request(req).await
     }
 
+    /// Lists the message templates of a WhatsApp Business Account, one page at a time.
+    /// Pass `TemplateList::next_cursor` to `TemplateFilters::after` to get the next page.
+    pub async fn list_message_templates(
+        &self,
+        whatsapp_business_id: &str,
+        filters: &TemplateFilters,
+    ) -> Result<TemplateList, WhatsappError> {
+        let url = self.message_templates_url(whatsapp_business_id);
+        let req = self.client.get(url).query(filters).build()?;
+
+        self.send_request(req).await
+    }
+
+    /// Lists the message templates of a WhatsApp Business Account, following every page
+    pub async fn list_all_message_templates(
+        &self,
+        whatsapp_business_id: &str,
+        filters: &TemplateFilters,
+    ) -> Result<Vec<TemplateDefinition>, WhatsappError> {
+        let mut filters = filters.clone();
+        let mut templates = Vec::new();
+        loop {
+            let page = self
+                .list_message_templates(whatsapp_business_id, &filters)
+                .await?;
+            let next_cursor = page.next_cursor().map(String::from);
+            templates.extend(page.data);
+            match next_cursor {
+                Some(cursor) => filters = filters.after(&cursor),
+                None => return Ok(templates),
+            }
+        }
+    }
+
+    pub async fn get_message_template(
+        &self,
+        template_id: &str,
+    ) -> Result<TemplateDefinition, WhatsappError> {
+        let url = format!("{}/{}", self.facebook_api_version_url(), template_id);
+        let req = self.client.get(url).build()?;
+
+        self.send_request(req).await
+    }
+
+    /// Submits a new message template for review
+    pub async fn create_message_template(
+        &self,
+        whatsapp_business_id: &str,
+        template: &TemplateDefinition,
+    ) -> Result<CreatedTemplate, WhatsappError> {
+        let url = self.message_templates_url(whatsapp_business_id);
+        let req = self.client.post(url).json(template).build()?;
+
+        self.send_request(req).await
+    }
+
+    pub async fn edit_message_template(
+        &self,
+        template_id: &str,
+        edit: &TemplateEdit,
+    ) -> Result<Success, WhatsappError> {
+        let url = format!("{}/{}", self.facebook_api_version_url(), template_id);
+        let req = self.client.post(url).json(edit).build()?;
+
+        self.send_request(req).await
+    }
+
+    /// Deletes a message template. Without `hsm_id` (the template id), every
+    /// language of the template `name` is deleted.
+    pub async fn delete_message_template(
+        &self,
+        whatsapp_business_id: &str,
+        name: &str,
+        hsm_id: Option<&str>,
+    ) -> Result<Success, WhatsappError> {
+        let url = self.message_templates_url(whatsapp_business_id);
+        let mut req = self.client.delete(url).query(&[("name", name)]);
+        if let Some(hsm_id) = hsm_id {
+            req = req.query(&[("hsm_id", hsm_id)]);
+        }
+        self.send_request(req.build()?).await
+    }
+
+    fn message_templates_url(&self, whatsapp_business_id: &str) -> String {
+        format!(
+            "{}/{}/message_templates",
+            self.facebook_api_version_url(),
+            whatsapp_business_id
+        )
+    }
+
     fn product_catalogs_url(&self, whatsapp_business_id: &str) -> String {
         // {whatsapp_business_id}/product_catalogs'
         format!(
@@ -248,7 +601,7 @@ impl WhatsappClient {
     }
 
     fn facebook_api_version_url(&self) -> String {
-        format!("{FACEBOOK_GRAPH_API_BASE_URL}/{}", self.version)
+        format!("{}/{}", self.base_url, self.version)
     }
 
     fn messages_api_url(&self, phone_number_id: &str) -> String {
@@ -263,6 +616,18 @@ impl WhatsappClient {
         format!("{}/{media_id}", self.facebook_api_version_url())
     }
 
+    fn upload_session_url(&self, app_id: &str) -> String {
+        format!("{}/{}/uploads", self.facebook_api_version_url(), app_id)
+    }
+
+    fn upload_media_api_url(&self, phone_number_id: &str) -> String {
+        format!(
+            "{}/{}/media",
+            self.facebook_api_version_url(),
+            phone_number_id
+        )
+    }
+
     fn request_code_api_url(&self, phone_number_id: &str) -> String {
         format!(
             "{}/{}/request_code",
@@ -313,10 +678,74 @@ impl WhatsappClient {
         url
     }
 
-    async fn send_request<Res>(&self, request: Request) -> Result<Res, WhatsappError>
+    /// The Resumable Upload API expects the `OAuth` scheme instead of `Bearer`
+    fn with_oauth_header(&self, mut request: Request) -> Result<Request, WhatsappError> {
+        let value =
+            HeaderValue::from_str(&format!("OAuth {}", self.access_token)).map_err(|_| {
+                WhatsappError::ConfigurationError(
+                    "could not parse auth token into a valid request header".into(),
+                )
+            })?;
+        request.headers_mut().insert(AUTHORIZATION, value);
+        Ok(request)
+    }
+
+    fn authorization_header(&self) -> Result<HeaderValue, WhatsappError> {
+        HeaderValue::from_str(&format!("Bearer {}", self.access_token)).map_err(|_| {
+            WhatsappError::ConfigurationError(
+                "could not parse auth token into a valid request header".into(),
+            )
+        })
+    }
+
+    async fn send_request<Res>(&self, mut request: Request) -> Result<Res, WhatsappError>
     where
         Res: serde::de::DeserializeOwned,
     {
+        let max_attempts = self.retry_policy.max_attempts();
+        let mut attempt = 1;
+
+        loop {
+            log::debug!(
+                "{} {} (attempt {attempt}/{max_attempts})",
+                request.method(),
+                request.url().path()
+            );
+
+            let method = request.method().clone();
+            let next_request = if attempt < max_attempts {
+                request.try_clone()
+            } else {
+                None
+            };
+
+            let error = match self.execute_request(request).await {
+                Err(error) if next_request.is_some() => error,
+                result => return result,
+            };
+            if !self.retry_policy.should_retry(&error, &method) {
+                return Err(error);
+            }
+
+            let retry_after = error.api_error().and_then(|e| e.retry_after);
+            let delay = self.retry_policy.delay(attempt, retry_after);
+            log::warn!("Attempt {attempt}/{max_attempts} failed: {error}. Retrying in {delay:?}");
+            tokio::time::sleep(delay).await;
+
+            request = next_request.expect("request is cloned when another attempt is left");
+            attempt += 1;
+        }
+    }
+
+    async fn execute_request<Res>(&self, mut request: Request) -> Result<Res, WhatsappError>
+    where
+        Res: serde::de::DeserializeOwned,
+    {
+        if !request.headers().contains_key(AUTHORIZATION) {
+            request
+                .headers_mut()
+                .insert(AUTHORIZATION, self.authorization_header()?);
+        }
         let response = self.client.execute(request).await?;
 
         match response.status() {
@@ -324,12 +753,45 @@ impl WhatsappClient {
                 let data = response.json::<Res>().await?;
                 Ok(data)
             }
-            _ => {
-                log::warn!("{:?}", &response);
-                let error_text = &response.text().await?;
-                log::warn!("{:?}", &error_text);
-                Err(WhatsappError::UnexpectedError(error_text.to_string()))
-            }
+            _ => Err(response_error(response).await),
         }
     }
 }
+
+/// Error of a failed response, parsed as a Graph API error when possible
+async fn response_error(response: Response) -> WhatsappError {
+    log::warn!("{:?}", &response);
+    let status = response.status();
+    let retry_after = response
+        .headers()
+        .get(RETRY_AFTER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(parse_retry_after);
+    let error_text = match response.text().await {
+        Ok(error_text) => error_text,
+        Err(error) => return error.into(),
+    };
+    log::warn!("{:?}", &error_text);
+    match serde_json::from_str::<ApiErrorResponse>(&error_text) {
+        Ok(ApiErrorResponse { mut error }) => {
+            error.status = status.as_u16();
+            error.retry_after = retry_after;
+            WhatsappError::Api(Box::new(error))
+        }
+        Err(_) => WhatsappError::UnexpectedError(error_text),
+    }
+}
+
+/// Parses a Retry-After header, given either in seconds or as an HTTP date
+fn parse_retry_after(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let date = httpdate::parse_http_date(value).ok()?;
+    // A date in the past means the request can be retried right away
+    Some(
+        date.duration_since(SystemTime::now())
+            .unwrap_or(Duration::ZERO),
+    )
+}
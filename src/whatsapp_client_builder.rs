@@ -0,0 +1,126 @@
+use std::time::Duration;
+
+use reqwest::header::HeaderValue;
+
+use crate::{
+    whatsapp_client::{FACEBOOK_GRAPH_API_BASE_URL, FACEBOOK_GRAPH_API_VERSION},
+    RetryPolicy, WhatsappClient, WhatsappError,
+};
+
+/// Builder for a [`WhatsappClient`], created with [`WhatsappClient::builder`]
+#[derive(Debug)]
+pub struct WhatsappClientBuilder {
+    access_token: String,
+    base_url: String,
+    version: String,
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    proxy: Option<reqwest::Proxy>,
+    user_agent: Option<String>,
+    client: Option<reqwest::Client>,
+    retry_policy: RetryPolicy,
+    validate_messages: bool,
+}
+
+impl WhatsappClientBuilder {
+    pub fn new(access_token: &str) -> Self {
+        Self {
+            access_token: access_token.into(),
+            base_url: FACEBOOK_GRAPH_API_BASE_URL.into(),
+            version: FACEBOOK_GRAPH_API_VERSION.into(),
+            timeout: None,
+            connect_timeout: None,
+            proxy: None,
+            user_agent: None,
+            client: None,
+            retry_policy: RetryPolicy::none(),
+            validate_messages: false,
+        }
+    }
+
+    /// Graph API base url, without the version. Defaults to `https://graph.facebook.com`
+    pub fn base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').into();
+        self
+    }
+
+    pub fn version(mut self, version: &str) -> Self {
+        self.version = version.into();
+        self
+    }
+
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub fn connect_timeout(mut self, connect_timeout: Duration) -> Self {
+        self.connect_timeout = Some(connect_timeout);
+        self
+    }
+
+    pub fn proxy(mut self, proxy: reqwest::Proxy) -> Self {
+        self.proxy = Some(proxy);
+        self
+    }
+
+    pub fn user_agent(mut self, user_agent: &str) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// Use a pre-built http client. Timeouts, proxy and user agent set on this
+    /// builder are ignored, as they must be configured on the given client.
+    pub fn client(mut self, client: reqwest::Client) -> Self {
+        self.client = Some(client);
+        self
+    }
+
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Validate messages against the Cloud API limits before sending them
+    pub fn validate_messages(mut self, validate_messages: bool) -> Self {
+        self.validate_messages = validate_messages;
+        self
+    }
+
+    pub fn build(self) -> Result<WhatsappClient, WhatsappError> {
+        HeaderValue::from_str(&format!("Bearer {}", self.access_token)).map_err(|_| {
+            WhatsappError::ConfigurationError(
+                "could not parse auth token into a valid request header".into(),
+            )
+        })?;
+
+        let client = match self.client {
+            Some(client) => client,
+            None => {
+                let mut builder = reqwest::Client::builder();
+                if let Some(timeout) = self.timeout {
+                    builder = builder.timeout(timeout);
+                }
+                if let Some(connect_timeout) = self.connect_timeout {
+                    builder = builder.connect_timeout(connect_timeout);
+                }
+                if let Some(proxy) = self.proxy {
+                    builder = builder.proxy(proxy);
+                }
+                if let Some(user_agent) = self.user_agent {
+                    builder = builder.user_agent(user_agent);
+                }
+                builder.build()?
+            }
+        };
+
+        Ok(WhatsappClient::from_parts(
+            self.base_url,
+            self.version,
+            self.access_token,
+            client,
+            self.retry_policy,
+            self.validate_messages,
+        ))
+    }
+}
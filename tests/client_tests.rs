@@ -0,0 +1,266 @@
+mod common;
+
+use std::time::Duration;
+
+use whatsapp_business_cloud_api::{
+    models::{Message, Text},
+    ApiErrorCategory, RetryPolicy, WhatsappClient, WhatsappError,
+};
+
+const MESSAGE_RESPONSE: &str = r#"{
+    "messaging_product": "whatsapp",
+    "contacts": [{ "input": "16505555555", "wa_id": "16505555555" }],
+    "messages": [{ "id": "wamid.HBgLMTY1MDUwNzY1MjAVAgARGBI5QTNDQTVCM0Q0Q0Q2RTY3RTcA" }]
+}"#;
+
+#[tokio::test]
+async fn builder_uses_base_url_and_version() -> Result<(), WhatsappError> {
+    let (url, server) = common::serve(vec![common::json_response(200, MESSAGE_RESPONSE)]).await;
+    let client = WhatsappClient::builder("token")
+        .base_url(&url)
+        .version("v21.0")
+        .timeout(Duration::from_secs(5))
+        .connect_timeout(Duration::from_secs(1))
+        .user_agent("whatsapp-tests")
+        .build()?;
+
+    let message = Message::from_text("16505555555", Text::new("hello"), None);
+    let response = client.send_message("1234".into(), &message).await?;
+    assert_eq!(response.messages.len(), 1);
+
+    let requests = server.await.unwrap();
+    assert_eq!(
+        requests[0].request_line(),
+        "POST /v21.0/1234/messages HTTP/1.1"
+    );
+    assert_eq!(requests[0].header("authorization"), Some("Bearer token"));
+    assert_eq!(requests[0].header("user-agent"), Some("whatsapp-tests"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn builder_rejects_invalid_token() {
+    let result = WhatsappClient::builder("invalid\ntoken").build();
+    assert!(matches!(result, Err(WhatsappError::ConfigurationError(_))));
+}
+
+#[tokio::test]
+async fn graph_error_is_parsed() -> Result<(), WhatsappError> {
+    let error_body = r#"{
+        "error": {
+            "message": "(#131047) Re-engagement message",
+            "type": "OAuthException",
+            "code": 131047,
+            "error_data": {
+                "messaging_product": "whatsapp",
+                "details": "Message failed to send because more than 24 hours have passed"
+            },
+            "fbtrace_id": "Az8or2yhqkZfEZ-_4Qn_Bam"
+        }
+    }"#;
+    let (url, _server) = common::serve(vec![common::json_response(400, error_body)]).await;
+    let client = WhatsappClient::builder("token").base_url(&url).build()?;
+
+    let message = Message::from_text("16505555555", Text::new("hello"), None);
+    let error = client
+        .send_message("1234".into(), &message)
+        .await
+        .unwrap_err();
+
+    let api_error = error.api_error().expect("expected a graph api error");
+    assert_eq!(api_error.status, 400);
+    assert_eq!(api_error.code, 131047);
+    assert_eq!(api_error.category(), Some(ApiErrorCategory::Recipient));
+    assert_eq!(
+        api_error.fbtrace_id.as_deref(),
+        Some("Az8or2yhqkZfEZ-_4Qn_Bam")
+    );
+    assert!(!error.is_retryable());
+    Ok(())
+}
+
+const THROTTLING_ERROR: &str = r#"{
+    "error": {
+        "message": "(#130429) Rate limit hit",
+        "type": "OAuthException",
+        "code": 130429,
+        "fbtrace_id": "Az8or2yhqkZfEZ-_4Qn_Bam"
+    }
+}"#;
+
+const SERVER_ERROR: &str = r#"{
+    "error": {
+        "message": "(#131000) Something went wrong",
+        "type": "OAuthException",
+        "code": 131000,
+        "fbtrace_id": "Az8or2yhqkZfEZ-_4Qn_Bam"
+    }
+}"#;
+
+fn retry_policy() -> RetryPolicy {
+    RetryPolicy::new(3)
+        .base_delay(Duration::from_millis(10))
+        .jitter(0.0)
+}
+
+#[tokio::test]
+async fn throttled_message_is_retried() -> Result<(), WhatsappError> {
+    let (url, server) = common::serve(vec![
+        common::response(
+            400,
+            &[("Content-Type", "application/json"), ("Retry-After", "0")],
+            THROTTLING_ERROR.as_bytes(),
+        ),
+        common::json_response(200, MESSAGE_RESPONSE),
+    ])
+    .await;
+    let client = WhatsappClient::builder("token")
+        .base_url(&url)
+        .retry_policy(retry_policy())
+        .build()?;
+
+    let message = Message::from_text("16505555555", Text::new("hello"), None);
+    client.send_message("1234".into(), &message).await?;
+
+    let requests = server.await.unwrap();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[0].body, requests[1].body);
+    Ok(())
+}
+
+#[tokio::test]
+async fn retry_after_http_date_is_honoured() -> Result<(), WhatsappError> {
+    let (url, server) = common::serve(vec![
+        common::response(
+            400,
+            &[
+                ("Content-Type", "application/json"),
+                ("Retry-After", "Wed, 21 Oct 2015 07:28:00 GMT"),
+            ],
+            THROTTLING_ERROR.as_bytes(),
+        ),
+        common::json_response(200, MESSAGE_RESPONSE),
+    ])
+    .await;
+    // The backoff delay is far longer than the timeout, so only the date can be used
+    let client = WhatsappClient::builder("token")
+        .base_url(&url)
+        .retry_policy(
+            retry_policy()
+                .base_delay(Duration::from_secs(60))
+                .max_delay(Duration::from_secs(60)),
+        )
+        .build()?;
+
+    let message = Message::from_text("16505555555", Text::new("hello"), None);
+    let sent = tokio::time::timeout(
+        Duration::from_secs(5),
+        client.send_message("1234".into(), &message),
+    )
+    .await;
+    assert!(matches!(sent, Ok(Ok(_))));
+
+    let requests = server.await.unwrap();
+    assert_eq!(requests.len(), 2);
+    Ok(())
+}
+
+#[tokio::test]
+async fn retry_after_is_capped_by_max_delay() -> Result<(), WhatsappError> {
+    let (url, server) = common::serve(vec![
+        common::response(
+            400,
+            &[
+                ("Content-Type", "application/json"),
+                ("Retry-After", "86400"),
+            ],
+            THROTTLING_ERROR.as_bytes(),
+        ),
+        common::json_response(200, MESSAGE_RESPONSE),
+    ])
+    .await;
+    let client = WhatsappClient::builder("token")
+        .base_url(&url)
+        .retry_policy(retry_policy().max_delay(Duration::from_millis(20)))
+        .build()?;
+
+    let message = Message::from_text("16505555555", Text::new("hello"), None);
+    let sent = tokio::time::timeout(
+        Duration::from_secs(5),
+        client.send_message("1234".into(), &message),
+    )
+    .await;
+    assert!(matches!(sent, Ok(Ok(_))));
+
+    let requests = server.await.unwrap();
+    assert_eq!(requests.len(), 2);
+    Ok(())
+}
+
+#[tokio::test]
+async fn server_error_is_not_retried_for_message_send() -> Result<(), WhatsappError> {
+    let (url, _server) = common::serve(vec![common::json_response(500, SERVER_ERROR)]).await;
+    let client = WhatsappClient::builder("token")
+        .base_url(&url)
+        .retry_policy(retry_policy())
+        .build()?;
+
+    let message = Message::from_text("16505555555", Text::new("hello"), None);
+    let error = client
+        .send_message("1234".into(), &message)
+        .await
+        .unwrap_err();
+    assert!(error.is_retryable());
+    assert_eq!(error.api_error().map(|e| e.code), Some(131000));
+    Ok(())
+}
+
+#[tokio::test]
+async fn server_error_is_retried_for_get() -> Result<(), WhatsappError> {
+    let media = r#"{
+        "messaging_product": "whatsapp",
+        "url": "https://lookaside.fbsbx.com/whatsapp_business/attachments/?mid=1",
+        "mime_type": "image/jpeg",
+        "sha256": "abc",
+        "file_size": 3,
+        "id": "1037543291543636"
+    }"#;
+    let (url, server) = common::serve(vec![
+        common::json_response(500, SERVER_ERROR),
+        common::json_response(200, media),
+    ])
+    .await;
+    let client = WhatsappClient::builder("token")
+        .base_url(&url)
+        .retry_policy(retry_policy())
+        .build()?;
+
+    let response = client.get_media("1037543291543636", None).await?;
+    assert_eq!(response.id, "1037543291543636");
+    assert_eq!(server.await.unwrap().len(), 2);
+    Ok(())
+}
+
+#[tokio::test]
+async fn messages_are_sent_in_order() -> Result<(), WhatsappError> {
+    let (url, server) = common::serve(vec![
+        common::json_response(200, MESSAGE_RESPONSE),
+        common::json_response(200, MESSAGE_RESPONSE),
+    ])
+    .await;
+    let client = WhatsappClient::builder("token").base_url(&url).build()?;
+
+    let messages = vec![
+        Message::from_text("16505555555", Text::new("first"), None),
+        Message::from_text("16505555555", Text::new("second"), None),
+    ];
+    let responses = client
+        .send_messages_in_order("1234".into(), &messages)
+        .await?;
+    assert_eq!(responses.len(), 2);
+
+    let requests = server.await.unwrap();
+    assert!(requests[0].body_text().contains("first"));
+    assert!(requests[1].body_text().contains("second"));
+    Ok(())
+}
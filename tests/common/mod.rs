@@ -0,0 +1,133 @@
+#![allow(dead_code)]
+
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+    task::JoinHandle,
+};
+
+/// Request as received by the mock server
+#[derive(Debug)]
+pub struct RecordedRequest {
+    pub head: String,
+    pub body: Vec<u8>,
+}
+
+impl RecordedRequest {
+    pub fn request_line(&self) -> &str {
+        self.head.lines().next().unwrap_or_default()
+    }
+
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.head.lines().skip(1).find_map(|line| {
+            let (key, value) = line.split_once(':')?;
+            key.eq_ignore_ascii_case(name).then(|| value.trim())
+        })
+    }
+
+    pub fn body_text(&self) -> String {
+        String::from_utf8_lossy(&self.body).into_owned()
+    }
+}
+
+pub fn json_response(status: u16, body: &str) -> String {
+    response(
+        status,
+        &[("Content-Type", "application/json")],
+        body.as_bytes(),
+    )
+}
+
+pub fn response(status: u16, headers: &[(&str, &str)], body: &[u8]) -> String {
+    let mut response = format!("HTTP/1.1 {status} Status\r\n");
+    for (name, value) in headers {
+        response.push_str(&format!("{name}: {value}\r\n"));
+    }
+    response.push_str(&format!(
+        "Content-Length: {}\r\nConnection: close\r\n\r\n",
+        body.len()
+    ));
+    response.push_str(&String::from_utf8_lossy(body));
+    response
+}
+
+/// Starts a server on a random local port that answers each incoming
+/// connection with the next canned response, and returns its base url
+/// together with a handle resolving to the recorded requests.
+pub async fn serve(responses: Vec<String>) -> (String, JoinHandle<Vec<RecordedRequest>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+
+    let handle = tokio::spawn(async move {
+        let mut requests = Vec::new();
+        for response in responses {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            requests.push(read_request(&mut stream).await);
+            stream.write_all(response.as_bytes()).await.unwrap();
+            stream.shutdown().await.ok();
+        }
+        requests
+    });
+
+    (url, handle)
+}
+
+async fn read_request(stream: &mut TcpStream) -> RecordedRequest {
+    let mut buffer = Vec::new();
+    let mut chunk = [0u8; 4096];
+
+    let head_end = loop {
+        let n = stream.read(&mut chunk).await.unwrap();
+        buffer.extend_from_slice(&chunk[..n]);
+        if let Some(pos) = buffer.windows(4).position(|w| w == b"\r\n\r\n") {
+            break pos + 4;
+        }
+        if n == 0 {
+            break buffer.len();
+        }
+    };
+
+    let head = String::from_utf8_lossy(&buffer[..head_end]).into_owned();
+    let mut body = buffer[head_end..].to_vec();
+    let request = RecordedRequest {
+        head,
+        body: Vec::new(),
+    };
+
+    if let Some(length) = request.header("content-length") {
+        let length: usize = length.parse().unwrap();
+        while body.len() < length {
+            let n = stream.read(&mut chunk).await.unwrap();
+            if n == 0 {
+                break;
+            }
+            body.extend_from_slice(&chunk[..n]);
+        }
+    } else if request.header("transfer-encoding") == Some("chunked") {
+        while !body.ends_with(b"0\r\n\r\n") {
+            let n = stream.read(&mut chunk).await.unwrap();
+            if n == 0 {
+                break;
+            }
+            body.extend_from_slice(&chunk[..n]);
+        }
+        body = decode_chunked(&body);
+    }
+
+    RecordedRequest { body, ..request }
+}
+
+fn decode_chunked(mut data: &[u8]) -> Vec<u8> {
+    let mut decoded = Vec::new();
+    while let Some(pos) = data.windows(2).position(|w| w == b"\r\n") {
+        let size = usize::from_str_radix(std::str::from_utf8(&data[..pos]).unwrap().trim(), 16)
+            .unwrap_or(0);
+        if size == 0 {
+            break;
+        }
+        let start = pos + 2;
+        decoded.extend_from_slice(&data[start..start + size]);
+        data = &data[start + size + 2..];
+    }
+    decoded
+}
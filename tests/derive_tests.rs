@@ -0,0 +1,53 @@
+use whatsapp_business_cloud_api::models::{Currency, Media, Template};
+use whatsapp_business_cloud_api_derive::WhatsappTemplate;
+
+#[derive(WhatsappTemplate)]
+#[template(name = "order_shipped", language = "en_US")]
+struct OrderShipped {
+    #[header(image)]
+    picture: Media,
+    #[body(2)]
+    order_number: u32,
+    #[body(1)]
+    name: String,
+    #[body(3, currency)]
+    total: Currency,
+    #[button(0, url)]
+    tracking: String,
+}
+
+#[derive(WhatsappTemplate)]
+#[template(name = "hello_world", language = "en_US")]
+struct HelloWorld;
+
+#[test]
+fn derived_template_builds_components() {
+    let template: Template = OrderShipped {
+        picture: Media::for_id("1"),
+        order_number: 42,
+        name: "Pablo".into(),
+        total: Currency::new("$100.99", "USD", 100990),
+        tracking: "track-42".into(),
+    }
+    .into();
+
+    let value = serde_json::to_value(template).unwrap();
+    assert_eq!(value["name"], "order_shipped");
+    assert_eq!(value["language"]["code"], "en_US");
+    let components = &value["components"];
+    assert_eq!(components[0]["type"], "header");
+    assert_eq!(components[0]["parameters"][0]["type"], "image");
+    assert_eq!(components[0]["parameters"][0]["image"]["id"], "1");
+    assert_eq!(components[1]["type"], "body");
+    assert_eq!(components[1]["parameters"][0]["text"], "Pablo");
+    assert_eq!(components[1]["parameters"][1]["text"], "42");
+    assert_eq!(components[1]["parameters"][2]["type"], "currency");
+    assert_eq!(components[2]["type"], "button");
+    assert_eq!(components[2]["sub_type"], "url");
+    assert_eq!(components[2]["index"], 0);
+    assert_eq!(components[2]["parameters"][0]["text"], "track-42");
+
+    let value = serde_json::to_value(Template::from(HelloWorld)).unwrap();
+    assert_eq!(value["name"], "hello_world");
+    assert!(value["components"].is_null());
+}
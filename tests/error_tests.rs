@@ -0,0 +1,40 @@
+use whatsapp_business_cloud_api::{ApiError, ApiErrorCategory, WhatsappError};
+
+fn api_error(status: u16, code: i32) -> ApiError {
+    let json = format!(
+        r#"{{
+            "message": "(#{code}) Error",
+            "type": "OAuthException",
+            "code": {code},
+            "error_data": {{
+                "messaging_product": "whatsapp",
+                "details": "Some details"
+            }},
+            "fbtrace_id": "Az8or2yhqkZfEZ-_4Qn_Bam"
+        }}"#
+    );
+    let mut error: ApiError = serde_json::from_str(&json).unwrap();
+    error.status = status;
+    error
+}
+
+#[test]
+fn api_error_classification() {
+    let error = api_error(401, 190);
+    assert_eq!(error.category(), Some(ApiErrorCategory::Authorization));
+    assert_eq!(error.details(), Some("Some details"));
+    assert!(error.is_auth_error());
+    assert!(!error.is_retryable());
+
+    let error = WhatsappError::from(api_error(400, 130429));
+    assert!(error.is_rate_limited());
+    assert!(error.is_retryable());
+
+    let error = api_error(400, 131047);
+    assert_eq!(error.category(), Some(ApiErrorCategory::Recipient));
+    assert!(!error.is_retryable());
+
+    let error = api_error(503, 999999);
+    assert_eq!(error.category(), None);
+    assert!(error.is_retryable());
+}
@@ -0,0 +1,260 @@
+mod common;
+
+use whatsapp_business_cloud_api::{
+    models::{Image, MediaSource, Message},
+    MediaVerificationError, WhatsappClient, WhatsappError,
+};
+
+#[tokio::test]
+async fn upload_media_sends_multipart_form() -> Result<(), WhatsappError> {
+    let (url, server) = common::serve(vec![common::json_response(
+        200,
+        r#"{ "id": "1166846181421424" }"#,
+    )])
+    .await;
+    let client = WhatsappClient::builder("token").base_url(&url).build()?;
+
+    let response = client
+        .upload_media(
+            "1234".into(),
+            b"%PDF-1.4 invoice".to_vec(),
+            "application/pdf",
+            "invoice.pdf",
+        )
+        .await?;
+    assert_eq!(response.id, "1166846181421424");
+
+    let requests = server.await.unwrap();
+    assert!(requests[0]
+        .request_line()
+        .starts_with("POST /v20.0/1234/media "));
+    let body = requests[0].body_text();
+    assert!(body.contains("name=\"messaging_product\"\r\n\r\nwhatsapp"));
+    assert!(body.contains("name=\"type\"\r\n\r\napplication/pdf"));
+    assert!(body.contains("name=\"file\"; filename=\"invoice.pdf\""));
+    assert!(body.contains("%PDF-1.4 invoice"));
+
+    let message = Message::from_image("16505555555", Image::for_id(&response.id, None), None);
+    assert!(serde_json::to_string(&message)
+        .unwrap()
+        .contains("1166846181421424"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn upload_media_from_reader() -> Result<(), WhatsappError> {
+    let (url, server) = common::serve(vec![common::json_response(
+        200,
+        r#"{ "id": "1166846181421424" }"#,
+    )])
+    .await;
+    let client = WhatsappClient::builder("token").base_url(&url).build()?;
+
+    let reader = std::io::Cursor::new(b"\x89PNG receipt".to_vec());
+    client
+        .upload_media(
+            "1234".into(),
+            MediaSource::from_reader(reader),
+            "image/png",
+            "receipt.png",
+        )
+        .await?;
+
+    let requests = server.await.unwrap();
+    assert!(requests[0].body_text().contains("PNG receipt"));
+    Ok(())
+}
+
+fn media_response(url: &str, content: &[u8], sha256: &str) -> String {
+    let body = format!(
+        r#"{{
+            "messaging_product": "whatsapp",
+            "url": "{url}/whatsapp_business/attachments/?mid=1037543291543636",
+            "mime_type": "audio/ogg",
+            "sha256": "{sha256}",
+            "file_size": {},
+            "id": "1037543291543636"
+        }}"#,
+        content.len()
+    );
+    common::json_response(200, &body)
+}
+
+fn sha256_hex(content: &[u8]) -> String {
+    use sha2::{Digest, Sha256};
+    Sha256::digest(content)
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+#[tokio::test]
+async fn download_media_verifies_digest() -> Result<(), WhatsappError> {
+    let content = b"OggS voice note";
+    let (cdn_url, cdn) = common::serve(vec![common::response(
+        200,
+        &[("Content-Type", "audio/ogg")],
+        content,
+    )])
+    .await;
+    let (url, _server) = common::serve(vec![media_response(
+        &cdn_url,
+        content,
+        &sha256_hex(content),
+    )])
+    .await;
+    let client = WhatsappClient::builder("token").base_url(&url).build()?;
+
+    let bytes = client.download_media("1037543291543636").await?;
+    assert_eq!(bytes, content);
+
+    let requests = cdn.await.unwrap();
+    assert_eq!(requests[0].header("authorization"), Some("Bearer token"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn download_media_rejects_digest_mismatch() -> Result<(), WhatsappError> {
+    let content = b"OggS voice note";
+    let (cdn_url, _cdn) = common::serve(vec![common::response(
+        200,
+        &[("Content-Type", "audio/ogg")],
+        content,
+    )])
+    .await;
+    let (url, _server) = common::serve(vec![media_response(
+        &cdn_url,
+        content,
+        &sha256_hex(b"other"),
+    )])
+    .await;
+    let client = WhatsappClient::builder("token").base_url(&url).build()?;
+
+    let mut written = Vec::new();
+    let error = client
+        .download_media_to("1037543291543636", &mut written)
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        WhatsappError::MediaVerificationError(MediaVerificationError::Sha256Mismatch { .. })
+    ));
+    Ok(())
+}
+
+#[tokio::test]
+async fn download_media_stops_past_the_announced_size() -> Result<(), WhatsappError> {
+    let content = b"OggS voice note";
+    let (cdn_url, _cdn) = common::serve(vec![common::response(
+        200,
+        &[("Content-Type", "audio/ogg")],
+        b"OggS voice note, and then some more",
+    )])
+    .await;
+    let (url, _server) = common::serve(vec![media_response(
+        &cdn_url,
+        content,
+        &sha256_hex(content),
+    )])
+    .await;
+    let client = WhatsappClient::builder("token").base_url(&url).build()?;
+
+    let mut written = Vec::new();
+    let error = client
+        .download_media_to("1037543291543636", &mut written)
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        WhatsappError::MediaVerificationError(MediaVerificationError::FileSizeMismatch {
+            expected: 15,
+            ..
+        })
+    ));
+    assert!(written.len() <= content.len());
+    Ok(())
+}
+
+#[tokio::test]
+async fn download_media_parses_cdn_errors() -> Result<(), WhatsappError> {
+    let expired_token = r#"{
+        "error": {
+            "message": "Error validating access token: Session has expired",
+            "type": "OAuthException",
+            "code": 190,
+            "error_subcode": 463
+        }
+    }"#;
+    let content = b"OggS voice note";
+    let (cdn_url, _cdn) = common::serve(vec![common::json_response(401, expired_token)]).await;
+    let (url, _server) = common::serve(vec![media_response(
+        &cdn_url,
+        content,
+        &sha256_hex(content),
+    )])
+    .await;
+    let client = WhatsappClient::builder("token").base_url(&url).build()?;
+
+    let error = client.download_media("1037543291543636").await.unwrap_err();
+    let api_error = error.api_error().expect("Graph API error");
+    assert_eq!(api_error.status, 401);
+    assert!(error.is_auth_error());
+    Ok(())
+}
+
+#[tokio::test]
+async fn delete_media_checks_phone_number() -> Result<(), WhatsappError> {
+    let (url, server) =
+        common::serve(vec![common::json_response(200, r#"{ "success": true }"#)]).await;
+    let client = WhatsappClient::builder("token").base_url(&url).build()?;
+
+    let response = client
+        .delete_media("1037543291543636", Some("1234"))
+        .await?;
+    assert!(response.success);
+
+    let requests = server.await.unwrap();
+    assert_eq!(
+        requests[0].request_line(),
+        "DELETE /v20.0/1037543291543636?phone_number_id=1234 HTTP/1.1"
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn resumable_upload_resumes_from_offset() -> Result<(), WhatsappError> {
+    let server_error = r#"{
+        "error": { "message": "An unknown error occurred", "type": "OAuthException", "code": 1 }
+    }"#;
+    let (url, server) = common::serve(vec![
+        common::json_response(200, r#"{ "id": "upload:MTphdHRhY2htZW50" }"#),
+        common::json_response(500, server_error),
+        common::json_response(
+            200,
+            r#"{ "id": "upload:MTphdHRhY2htZW50", "file_offset": 4 }"#,
+        ),
+        common::json_response(200, r#"{ "h": "2:c2FtcGxlLm1wNA==:image/jpeg:GKAj0gA" }"#),
+    ])
+    .await;
+    let client = WhatsappClient::builder("token").base_url(&url).build()?;
+
+    let upload = client
+        .resumable_upload("4567", "logo.jpg", "image/jpeg", b"0123456789".to_vec())
+        .await?;
+    assert_eq!(upload.h, "2:c2FtcGxlLm1wNA==:image/jpeg:GKAj0gA");
+
+    let requests = server.await.unwrap();
+    assert_eq!(
+        requests[0].request_line(),
+        "POST /v20.0/4567/uploads?file_name=logo.jpg&file_length=10&file_type=image%2Fjpeg HTTP/1.1"
+    );
+    assert_eq!(requests[1].header("authorization"), Some("OAuth token"));
+    assert_eq!(requests[1].header("file_offset"), Some("0"));
+    assert_eq!(
+        requests[2].request_line(),
+        "GET /v20.0/upload:MTphdHRhY2htZW50 HTTP/1.1"
+    );
+    assert_eq!(requests[3].header("file_offset"), Some("4"));
+    assert_eq!(requests[3].body, b"456789");
+    Ok(())
+}
@@ -0,0 +1,376 @@
+use serde_json::json;
+use whatsapp_business_cloud_api::models::{
+    Audio, ContactCard, ContactName, ContactPhone, Context, Document, FlowActionParameters,
+    FormattedText, Image, Interactive, InteractiveActionButton, InteractiveActionSection,
+    InteractiveActionSectionProductItem, InteractiveFooter, InteractiveHeader, Location, Message,
+    MessageContent, Sticker, Text, Video,
+};
+
+#[test]
+fn media_messages_serialize_type_and_payload() {
+    let document = Document::new(
+        "https://example.com/invoice.pdf",
+        Some("Invoice #42".into()),
+        Some("invoice-42.pdf".into()),
+    );
+    let value =
+        serde_json::to_value(Message::from_document("16505555555", document, None)).unwrap();
+    assert_eq!(value["type"], "document");
+    assert_eq!(value["document"]["filename"], "invoice-42.pdf");
+    assert_eq!(value["document"]["link"], "https://example.com/invoice.pdf");
+
+    let value =
+        serde_json::to_value(Message::from_audio("16505555555", Audio::for_id("1"), None)).unwrap();
+    assert_eq!(value["type"], "audio");
+    assert_eq!(value["audio"]["id"], "1");
+
+    let value = serde_json::to_value(Message::from_video(
+        "16505555555",
+        Video::for_id("2", None),
+        None,
+    ))
+    .unwrap();
+    assert_eq!(value["type"], "video");
+
+    let value = serde_json::to_value(Message::from_sticker(
+        "16505555555",
+        Sticker::for_id("3"),
+        None,
+    ))
+    .unwrap();
+    assert_eq!(value["type"], "sticker");
+    assert_eq!(value["sticker"], json!({ "link": null, "id": "3" }));
+}
+
+#[test]
+fn location_and_contacts_messages() {
+    let location = Location::with_name_and_address(
+        37.483307,
+        122.148981,
+        "Pablo Morales",
+        "1 Hacker Way, Menlo Park, CA 94025",
+    );
+    let value =
+        serde_json::to_value(Message::from_location("16505555555", location, None)).unwrap();
+    assert_eq!(value["type"], "location");
+    assert_eq!(value["location"]["latitude"], 37.483307);
+    assert_eq!(value["location"]["name"], "Pablo Morales");
+
+    let card = ContactCard::with_phones(
+        ContactName::with_first_and_last_name("Support Team", "Support", "Team"),
+        vec![ContactPhone::with_wa_id(
+            "+1 (650) 555-1234",
+            "16505551234",
+            Some("WORK".into()),
+        )],
+    );
+    let value =
+        serde_json::to_value(Message::from_contacts("16505555555", vec![card], None)).unwrap();
+    assert_eq!(value["type"], "contacts");
+    assert_eq!(
+        value["contacts"][0]["name"]["formatted_name"],
+        "Support Team"
+    );
+    assert_eq!(value["contacts"][0]["phones"][0]["wa_id"], "16505551234");
+    assert_eq!(value["contacts"][0]["phones"][0]["type"], "WORK");
+}
+
+#[test]
+fn reaction_messages() {
+    let value =
+        serde_json::to_value(Message::from_reaction("16505555555", "wamid.1", "👍")).unwrap();
+    assert_eq!(value["type"], "reaction");
+    assert_eq!(
+        value["reaction"],
+        json!({ "message_id": "wamid.1", "emoji": "👍" })
+    );
+
+    let value = serde_json::to_value(Message::remove_reaction("16505555555", "wamid.1")).unwrap();
+    assert_eq!(value["reaction"]["emoji"], "");
+}
+
+#[test]
+fn interactive_header_and_footer() {
+    let interactive = Interactive::for_button(
+        vec![InteractiveActionButton::new("Track", "track")],
+        "Your order has shipped",
+    )
+    .with_header(InteractiveHeader::image(Image::for_id("1", None)))
+    .with_footer(InteractiveFooter::new("Reply STOP to opt out"));
+
+    let value =
+        serde_json::to_value(Message::from_interactive("16505555555", interactive, None)).unwrap();
+    assert_eq!(value["interactive"]["header"]["type"], "image");
+    assert_eq!(value["interactive"]["header"]["image"]["id"], "1");
+    assert_eq!(
+        value["interactive"]["footer"]["text"],
+        "Reply STOP to opt out"
+    );
+
+    let header = serde_json::to_value(InteractiveHeader::text("Order #42")).unwrap();
+    assert_eq!(header, json!({ "type": "text", "text": "Order #42" }));
+}
+
+#[test]
+fn product_list_message() {
+    let sections = vec![
+        InteractiveActionSection::with_product_items(
+            vec![
+                InteractiveActionSectionProductItem::new("sku-1"),
+                InteractiveActionSectionProductItem::new("sku-2"),
+            ],
+            "Shoes",
+        ),
+        InteractiveActionSection::with_product_items(
+            vec![InteractiveActionSectionProductItem::new("sku-3")],
+            "Socks",
+        ),
+    ];
+    let interactive =
+        Interactive::for_product_list("catalog", "Our picks", sections, "Curated for you").unwrap();
+    let value =
+        serde_json::to_value(Message::from_interactive("16505555555", interactive, None)).unwrap();
+    assert_eq!(value["interactive"]["type"], "product_list");
+    assert_eq!(value["interactive"]["header"]["text"], "Our picks");
+    assert_eq!(value["interactive"]["action"]["catalog_id"], "catalog");
+    assert_eq!(
+        value["interactive"]["action"]["sections"][1]["product_items"][0]["product_retailer_id"],
+        "sku-3"
+    );
+
+    let interactive = Interactive::for_product("catalog", "sku-1", "Back in stock");
+    let value = serde_json::to_value(interactive).unwrap();
+    assert_eq!(value["type"], "product");
+    assert_eq!(value["action"]["product_retailer_id"], "sku-1");
+}
+
+#[test]
+fn product_list_limits_are_enforced() {
+    let items = (0..31)
+        .map(|i| InteractiveActionSectionProductItem::new(&format!("sku-{i}")))
+        .collect();
+    let sections = vec![InteractiveActionSection::with_product_items(items, "All")];
+    assert!(Interactive::for_product_list("catalog", "Header", sections, "Body").is_err());
+
+    let sections = (0..11)
+        .map(|i| {
+            InteractiveActionSection::with_product_items(
+                vec![InteractiveActionSectionProductItem::new("sku")],
+                &format!("Section {i}"),
+            )
+        })
+        .collect();
+    assert!(Interactive::for_product_list("catalog", "Header", sections, "Body").is_err());
+}
+
+#[test]
+fn flow_message() {
+    let parameters = FlowActionParameters::for_flow_id("1234567890", "Book!")
+        .with_flow_token("AQAAAAACS5FpgQ_cAAAAAD0QI3s.")
+        .navigate("BOOKING", Some(json!({ "product_name": "name" })));
+    let interactive = Interactive::for_flow(parameters, "Book your appointment");
+    let value = serde_json::to_value(interactive).unwrap();
+    assert_eq!(value["type"], "flow");
+    assert_eq!(value["action"]["name"], "flow");
+    assert_eq!(
+        value["action"]["parameters"],
+        json!({
+            "flow_message_version": "3",
+            "flow_token": "AQAAAAACS5FpgQ_cAAAAAD0QI3s.",
+            "flow_id": "1234567890",
+            "flow_cta": "Book!",
+            "flow_action": "navigate",
+            "flow_action_payload": {
+                "screen": "BOOKING",
+                "data": { "product_name": "name" }
+            }
+        })
+    );
+}
+
+#[test]
+fn cta_url_and_location_request_messages() {
+    let interactive = Interactive::for_cta_url(
+        "Track order",
+        "https://example.com/track/42",
+        "Your order is on its way",
+    );
+    let value = serde_json::to_value(interactive).unwrap();
+    assert_eq!(value["type"], "cta_url");
+    assert_eq!(value["action"]["name"], "cta_url");
+    assert_eq!(
+        value["action"]["parameters"],
+        json!({ "display_text": "Track order", "url": "https://example.com/track/42" })
+    );
+
+    let interactive = Interactive::for_location_request("Where should we deliver?");
+    let value = serde_json::to_value(interactive).unwrap();
+    assert_eq!(value["type"], "location_request_message");
+    assert_eq!(value["action"]["name"], "send_location");
+}
+
+#[test]
+fn message_type_is_derived_from_content() {
+    let message = Message::from_text("16505555555", Text::new("hello"), None)
+        .with_context(Context::new("wamid.1"))
+        .with_biz_opaque_callback_data("order-42")
+        .with_recipient_type("individual");
+    let value = serde_json::to_value(&message).unwrap();
+    assert_eq!(value["type"], "text");
+    assert_eq!(value["text"]["body"], "hello");
+    assert_eq!(value["context"]["message_id"], "wamid.1");
+    assert_eq!(value["biz_opaque_callback_data"], "order-42");
+    assert_eq!(value["recipient_type"], "individual");
+    assert!(value.get("image").is_none());
+
+    let message: Message = serde_json::from_value(value).unwrap();
+    assert!(matches!(message.content(), MessageContent::Text { .. }));
+
+    let inconsistent = json!({
+        "messaging_product": "whatsapp",
+        "to": "16505555555",
+        "type": "text",
+        "image": { "id": "1" }
+    });
+    assert!(serde_json::from_value::<Message>(inconsistent).is_err());
+}
+
+#[test]
+fn formatted_text_builds_spans_and_blocks() {
+    let text = FormattedText::new()
+        .text("Hi ")
+        .bold("Jane Doe")
+        .text(", your order ")
+        .monospace("#42")
+        .text(" is ")
+        .italic(" on its way ")
+        .bulleted_list(&["2 x socks", "1 x hat"])
+        .numbered_list(&["Track it", "Rate us"])
+        .quote("Fast delivery!")
+        .text("> not a quote")
+        .build();
+    let value = serde_json::to_value(text).unwrap();
+    assert_eq!(
+        value["body"],
+        "Hi *Jane Doe*, your order ```#42``` is  _on its way_ \n\
+         - 2 x socks\n\
+         - 1 x hat\n\
+         1. Track it\n\
+         2. Rate us\n\
+         > Fast delivery!\n\
+         \u{200B}> not a quote"
+    );
+    assert!(value["preview_url"].is_null());
+
+    let text = FormattedText::new()
+        .text("See ")
+        .raw("*https://example.com*")
+        .build();
+    let value = serde_json::to_value(text).unwrap();
+    assert_eq!(value["body"], "See *https://example.com*");
+    assert_eq!(value["preview_url"], true);
+}
+
+#[test]
+fn formatted_text_neutralises_user_markup() {
+    let cases = [
+        (
+            "price is *not* final",
+            "price is *\u{200B}not\u{200B}* final",
+        ),
+        ("a * b * c", "a * b * c"),
+        ("**", "*\u{200B}*\u{200B}"),
+        (
+            "*bold*_italic_",
+            "*\u{200B}bold\u{200B}*_\u{200B}italic\u{200B}_",
+        ),
+        (
+            "see *this*, or _that_.",
+            "see *\u{200B}this\u{200B}*, or _\u{200B}that\u{200B}_.",
+        ),
+    ];
+    for (text, body) in cases {
+        assert_eq!(FormattedText::new().text(text).as_str(), body);
+    }
+}
+
+#[test]
+fn formatted_spans_keep_user_markup_inside() {
+    let formatted_text = FormattedText::new().italic("snake_case_name");
+    assert_eq!(formatted_text.as_str(), "_snake_case_name_");
+    let formatted_text = FormattedText::new().bold("a*b *c* d");
+    assert_eq!(formatted_text.as_str(), "*a*b *\u{200B}c\u{200B}* d*");
+    let formatted_text = FormattedText::new().bold("two\nlines");
+    assert_eq!(formatted_text.as_str(), "*two*\n*lines*");
+}
+
+#[test]
+fn formatted_spans_are_separated_from_words() {
+    let formatted_text = FormattedText::new().text("Hello").bold("world");
+    assert_eq!(formatted_text.as_str(), "Hello\u{200B}*world*");
+    let formatted_text = FormattedText::new().bold("x").text("abc");
+    assert_eq!(formatted_text.as_str(), "*x*\u{200B}abc");
+    let formatted_text = FormattedText::new()
+        .italic("x")
+        .bold("y")
+        .text(", ")
+        .bold("w ")
+        .text("v");
+    assert_eq!(formatted_text.as_str(), "_x_*y*, *w* v");
+}
+
+#[test]
+fn split_text_on_paragraphs() {
+    let first = "word ".repeat(600);
+    let second = "another sentence. ".repeat(200);
+    let body = format!("{first}\n\n{second}");
+    let messages = Message::split_text("16505555555", &body, Some(Context::new("wamid.1")));
+    assert_eq!(messages.len(), 2);
+
+    let value = serde_json::to_value(&messages[0]).unwrap();
+    assert_eq!(value["text"]["body"], first.trim());
+    assert_eq!(value["context"]["message_id"], "wamid.1");
+    let value = serde_json::to_value(&messages[1]).unwrap();
+    assert_eq!(value["text"]["body"], second.trim());
+    assert!(value["context"].is_null());
+    assert!(messages.iter().all(|message| message.validate().is_empty()));
+}
+
+#[test]
+fn split_text_keeps_spans_and_graphemes() {
+    let body = format!("{}*bold text here* and more", "x ".repeat(2040));
+    let bodies: Vec<String> = Message::split_text("16505555555", &body, None)
+        .iter()
+        .map(|message| serde_json::to_value(message).unwrap()["text"]["body"].to_string())
+        .collect();
+    assert_eq!(bodies.len(), 2);
+    assert!(bodies[0].ends_with("x\""));
+    assert_eq!(bodies[1], "\"*bold text here* and more\"");
+
+    // A span that can't fit in one message is cut at a word boundary near the limit
+    let body = format!("Intro *{}* outro", "word ".repeat(1000).trim());
+    let bodies: Vec<String> = Message::split_text("16505555555", &body, None)
+        .iter()
+        .map(|message| serde_json::to_value(message).unwrap()["text"]["body"].to_string())
+        .collect();
+    assert_eq!(bodies.len(), 2);
+    assert!(bodies[0].chars().count() > 4000);
+    assert!(bodies[0].ends_with("word\""));
+    assert!(bodies[1].starts_with("\"word"));
+
+    let family = "👨‍👩‍👧";
+    let body = family.repeat(1000);
+    let messages = Message::split_text("16505555555", &body, None);
+    assert_eq!(messages.len(), 2);
+    for message in &messages {
+        let MessageContent::Text { text } = message.content() else {
+            panic!("expected a text message");
+        };
+        let body = serde_json::to_value(text).unwrap()["body"]
+            .as_str()
+            .unwrap()
+            .to_string();
+        assert!(body.chars().count() <= 4096);
+        assert_eq!(body.replace(family, ""), "");
+    }
+}
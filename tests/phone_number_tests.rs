@@ -4,3 +4,19 @@ fn setup() {
     dotenv::dotenv().ok();
     let _ = env_logger::builder().is_test(true).try_init();
 }
+
+#[tokio::test]
+#[ignore = "requires WhatsApp Cloud API credentials"]
+async fn request_code_works() -> Result<(), WhatsappError> {
+    setup();
+    let access_token = std::env::var("WHATSAPP_ACCESS_TOKEN")
+        .expect("Missing environment variable WHATSAPP_ACCESS_TOKEN");
+    let phone_number_id = std::env::var("WHATSAPP_PHONE_NUMBER_ID")
+        .expect("Missing environment variable WHATSAPP_PHONE_NUMBER_ID");
+    let client = WhatsappClient::new(&access_token);
+    let response = client
+        .request_code(phone_number_id, CodeMethod::SMS, "en_US")
+        .await?;
+    assert_eq!(response.success, Some(true));
+    Ok(())
+}
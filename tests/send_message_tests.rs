@@ -10,3 +10,83 @@ fn setup() {
     dotenv::dotenv().ok();
     let _ = env_logger::builder().is_test(true).try_init();
 }
+
+fn client_and_recipient() -> (WhatsappClient, String, String) {
+    setup();
+    let access_token = std::env::var("WHATSAPP_ACCESS_TOKEN")
+        .expect("Missing environment variable WHATSAPP_ACCESS_TOKEN");
+    let phone_number_id = std::env::var("WHATSAPP_PHONE_NUMBER_ID")
+        .expect("Missing environment variable WHATSAPP_PHONE_NUMBER_ID");
+    let to =
+        std::env::var("WHATSAPP_SEND_TO").expect("Missing environment variable WHATSAPP_SEND_TO");
+    (WhatsappClient::new(&access_token), phone_number_id, to)
+}
+
+#[tokio::test]
+#[ignore = "requires WhatsApp Cloud API credentials"]
+async fn send_text_message_works() -> Result<(), WhatsappError> {
+    let (client, phone_number_id, to) = client_and_recipient();
+    let text = Text::new("test message");
+    let message = Message::from_text(&to, text, None);
+    let response = client.send_message(phone_number_id, &message).await?;
+    assert_eq!(response.messages.len(), 1);
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore = "requires WhatsApp Cloud API credentials"]
+async fn send_template_message_works() -> Result<(), WhatsappError> {
+    let (client, phone_number_id, to) = client_and_recipient();
+    let parameters = Vec::from([Parameter::from_text("3")]);
+    let components = Vec::from([Component::with_parameters(ComponentType::Body, parameters)]);
+    let template = Template::with_components("sample_shipping_confirmation", "en_US", components);
+    let message = Message::from_template(&to, template, None);
+    let response = client.send_message(phone_number_id, &message).await?;
+    assert_eq!(response.messages.len(), 1);
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore = "requires WhatsApp Cloud API credentials"]
+async fn send_image_message_works() -> Result<(), WhatsappError> {
+    let (client, phone_number_id, to) = client_and_recipient();
+    let image = Image::new(
+        "https://www.rust-lang.org/static/images/rust-logo-blk.svg",
+        Some("Rust".into()),
+    );
+    let message = Message::from_image(&to, image, None);
+    let response = client.send_message(phone_number_id, &message).await?;
+    assert_eq!(response.messages.len(), 1);
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore = "requires WhatsApp Cloud API credentials"]
+async fn send_interactive_button_message_works() -> Result<(), WhatsappError> {
+    let (client, phone_number_id, to) = client_and_recipient();
+    let buttons = vec![
+        InteractiveActionButton::new("Yes", "yes"),
+        InteractiveActionButton::new("No", "no"),
+    ];
+    let interactive = Interactive::for_button(buttons, "Do you like Rust?");
+    let message = Message::from_interactive(&to, interactive, None);
+    let response = client.send_message(phone_number_id, &message).await?;
+    assert_eq!(response.messages.len(), 1);
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore = "requires WhatsApp Cloud API credentials"]
+async fn send_interactive_list_message_works() -> Result<(), WhatsappError> {
+    let (client, phone_number_id, to) = client_and_recipient();
+    let rows = vec![
+        InteractiveActionSectionRow::new("rust", "Rust"),
+        InteractiveActionSectionRow::with_description("go", "Go", "Not Rust"),
+    ];
+    let sections = vec![InteractiveActionSection::with_title(rows, "Languages")];
+    let interactive = Interactive::for_list("Choose", sections, "Pick a language");
+    let message = Message::from_interactive(&to, interactive, None);
+    let response = client.send_message(phone_number_id, &message).await?;
+    assert_eq!(response.messages.len(), 1);
+    Ok(())
+}
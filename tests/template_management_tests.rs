@@ -0,0 +1,206 @@
+mod common;
+
+use serde_json::json;
+use whatsapp_business_cloud_api::{
+    models::{
+        HeaderFormat, TemplateButton, TemplateCategory, TemplateComponent, TemplateDefinition,
+        TemplateExample, TemplateFilters, TemplateStatus,
+    },
+    WhatsappClient, WhatsappError,
+};
+
+#[tokio::test]
+async fn list_all_message_templates_follows_pages() -> Result<(), WhatsappError> {
+    let first_page = r#"{
+        "data": [{
+            "id": "594425479261596",
+            "name": "order_confirmation",
+            "language": "en_US",
+            "status": "APPROVED",
+            "category": "UTILITY",
+            "components": [
+                { "type": "HEADER", "format": "TEXT", "text": "Order {{1}}" },
+                { "type": "BODY", "text": "Hi {{1}}, your order is confirmed.",
+                  "example": { "body_text": [["Pablo"]] } },
+                { "type": "BUTTONS", "buttons": [
+                    { "type": "URL", "text": "Track", "url": "https://example.com/{{1}}" }
+                ] }
+            ]
+        }],
+        "paging": {
+            "cursors": { "before": "MAZDZD", "after": "MjQZD" },
+            "next": "https://graph.facebook.com/v20.0/102290129340398/message_templates?after=MjQZD"
+        }
+    }"#;
+    let second_page = r#"{
+        "data": [{
+            "id": "1192339204654487",
+            "name": "seasonal_promotion",
+            "language": "en",
+            "status": "PENDING",
+            "category": "MARKETING",
+            "components": [{ "type": "FOOTER", "text": "Reply STOP to opt out" }]
+        }],
+        "paging": { "cursors": { "before": "MjQZD", "after": "MjUZD" } }
+    }"#;
+    let (url, server) = common::serve(vec![
+        common::json_response(200, first_page),
+        common::json_response(200, second_page),
+    ])
+    .await;
+    let client = WhatsappClient::builder("token").base_url(&url).build()?;
+
+    let filters = TemplateFilters::new()
+        .status(TemplateStatus::Approved)
+        .limit(1);
+    let templates = client
+        .list_all_message_templates("102290129340398", &filters)
+        .await?;
+    assert_eq!(templates.len(), 2);
+    assert_eq!(templates[0].category, TemplateCategory::Utility);
+    assert!(matches!(
+        &templates[0].components[2],
+        TemplateComponent::Buttons { buttons } if matches!(buttons[0], TemplateButton::Url { .. })
+    ));
+    assert_eq!(templates[1].status, Some(TemplateStatus::Pending));
+
+    let requests = server.await.unwrap();
+    assert_eq!(
+        requests[0].request_line(),
+        "GET /v20.0/102290129340398/message_templates?status=APPROVED&limit=1 HTTP/1.1"
+    );
+    assert_eq!(
+        requests[1].request_line(),
+        "GET /v20.0/102290129340398/message_templates?status=APPROVED&limit=1&after=MjQZD HTTP/1.1"
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn templates_with_unknown_types_are_listed() -> Result<(), WhatsappError> {
+    let page = r#"{
+        "data": [{
+            "id": "594425479261596",
+            "name": "summer_sale",
+            "language": "en_US",
+            "status": "UNDER_REVIEW",
+            "category": "MARKETING",
+            "components": [
+                { "type": "HEADER", "format": "GIF" },
+                { "type": "LIMITED_TIME_OFFER", "limited_time_offer": { "text": "Expiring!" } },
+                { "type": "BODY", "text": "Use code {{1}}" },
+                { "type": "BUTTONS", "buttons": [
+                    { "type": "COPY_CODE", "example": ["250FF"] },
+                    { "type": "VOICE_CALL", "text": "Call us" }
+                ] }
+            ]
+        }]
+    }"#;
+    let (url, _server) = common::serve(vec![common::json_response(200, page)]).await;
+    let client = WhatsappClient::builder("token").base_url(&url).build()?;
+
+    let templates = client
+        .list_message_templates("102290129340398", &TemplateFilters::new())
+        .await?;
+    let template = &templates.data[0];
+    assert_eq!(template.status, Some(TemplateStatus::Unknown));
+    assert!(matches!(
+        template.components[0],
+        TemplateComponent::Header {
+            format: HeaderFormat::Unknown,
+            ..
+        }
+    ));
+    assert!(matches!(template.components[1], TemplateComponent::Unknown));
+    let TemplateComponent::Buttons { buttons } = &template.components[3] else {
+        panic!("expected buttons, got {:?}", template.components[3]);
+    };
+    assert!(matches!(&buttons[0], TemplateButton::CopyCode { example } if example == "250FF"));
+    assert!(matches!(buttons[1], TemplateButton::Unknown));
+    Ok(())
+}
+
+#[tokio::test]
+async fn create_message_template() -> Result<(), WhatsappError> {
+    let (url, server) = common::serve(vec![common::json_response(
+        200,
+        r#"{ "id": "572279198452421", "status": "PENDING", "category": "MARKETING" }"#,
+    )])
+    .await;
+    let client = WhatsappClient::builder("token").base_url(&url).build()?;
+
+    let template = TemplateDefinition::new(
+        "seasonal_promotion",
+        "en_US",
+        TemplateCategory::Marketing,
+        vec![
+            TemplateComponent::media_header(
+                HeaderFormat::Image,
+                TemplateExample::for_header_handle("4::aW1hZ2UvanBlZw"),
+            ),
+            TemplateComponent::body(
+                "Shop now and use code {{1}} to get {{2}} off!",
+                Some(TemplateExample::for_body_text(vec![
+                    "25OFF".into(),
+                    "25%".into(),
+                ])),
+            ),
+            TemplateComponent::footer("Reply STOP to opt out"),
+            TemplateComponent::buttons(vec![TemplateButton::QuickReply {
+                text: "Unsubscribe".into(),
+            }]),
+        ],
+    );
+    let created = client
+        .create_message_template("102290129340398", &template)
+        .await?;
+    assert_eq!(created.id, "572279198452421");
+    assert_eq!(created.status, Some(TemplateStatus::Pending));
+
+    let requests = server.await.unwrap();
+    assert_eq!(
+        requests[0].request_line(),
+        "POST /v20.0/102290129340398/message_templates HTTP/1.1"
+    );
+    let body: serde_json::Value = serde_json::from_slice(&requests[0].body).unwrap();
+    assert_eq!(
+        body,
+        json!({
+            "name": "seasonal_promotion",
+            "language": "en_US",
+            "category": "MARKETING",
+            "components": [
+                { "type": "HEADER", "format": "IMAGE",
+                  "example": { "header_handle": ["4::aW1hZ2UvanBlZw"] } },
+                { "type": "BODY", "text": "Shop now and use code {{1}} to get {{2}} off!",
+                  "example": { "body_text": [["25OFF", "25%"]] } },
+                { "type": "FOOTER", "text": "Reply STOP to opt out" },
+                { "type": "BUTTONS", "buttons": [{ "type": "QUICK_REPLY", "text": "Unsubscribe" }] }
+            ]
+        })
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn delete_message_template_by_id() -> Result<(), WhatsappError> {
+    let (url, server) =
+        common::serve(vec![common::json_response(200, r#"{ "success": true }"#)]).await;
+    let client = WhatsappClient::builder("token").base_url(&url).build()?;
+
+    let response = client
+        .delete_message_template(
+            "102290129340398",
+            "order_confirmation",
+            Some("594425479261596"),
+        )
+        .await?;
+    assert!(response.success);
+
+    let requests = server.await.unwrap();
+    assert_eq!(
+        requests[0].request_line(),
+        "DELETE /v20.0/102290129340398/message_templates?name=order_confirmation&hsm_id=594425479261596 HTTP/1.1"
+    );
+    Ok(())
+}
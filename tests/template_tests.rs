@@ -0,0 +1,383 @@
+use serde_json::json;
+use whatsapp_business_cloud_api::models::{
+    CarouselCard, Component, ComponentSubType, ComponentType, Currency, DateTime, Location, Media,
+    Parameter, ParameterAction, RenderedButton, Template, TemplateDefinition, Violation,
+};
+
+#[test]
+fn template_parameters_serialize_by_kind() {
+    let components = vec![
+        Component::with_parameters(
+            ComponentType::Header,
+            vec![Parameter::from_location(Location::with_name_and_address(
+                37.483307,
+                122.148981,
+                "Pablo Morales",
+                "1 Hacker Way, Menlo Park, CA 94025",
+            ))],
+        ),
+        Component::with_parameters(
+            ComponentType::Body,
+            vec![
+                Parameter::from_text("Jessica").with_parameter_name("first_name"),
+                Parameter::from_currency(Currency::new("$100.99", "USD", 100990)),
+                Parameter::from_date_time(DateTime::new("February 25, 1977")),
+            ],
+        ),
+        Component::for_button(
+            ComponentType::Button,
+            ComponentSubType::CopyCode,
+            vec![Parameter::from_coupon_code("25OFF")],
+            0,
+        ),
+        Component::for_button(
+            ComponentType::Button,
+            ComponentSubType::Catalog,
+            vec![Parameter::from_action(ParameterAction::for_catalog(
+                "sku-1",
+            ))],
+            1,
+        ),
+        Component::for_button(
+            ComponentType::Button,
+            ComponentSubType::QuickReply,
+            vec![Parameter::from_payload("unsubscribe")],
+            2,
+        ),
+    ];
+    let template = Template::with_components("summer_sale", "en_US", components);
+    let value = serde_json::to_value(template).unwrap();
+    let components = &value["components"];
+
+    assert_eq!(components[0]["parameters"][0]["type"], "location");
+    assert_eq!(
+        components[0]["parameters"][0]["location"]["name"],
+        "Pablo Morales"
+    );
+    assert_eq!(
+        components[1]["parameters"][0]["parameter_name"],
+        "first_name"
+    );
+    assert_eq!(
+        components[1]["parameters"][1]["currency"]["amount_1000"],
+        100990
+    );
+    assert_eq!(
+        components[1]["parameters"][2]["date_time"],
+        json!({ "fallback_value": "February 25, 1977" })
+    );
+    assert_eq!(components[2]["sub_type"], "copy_code");
+    assert_eq!(components[2]["parameters"][0]["type"], "coupon_code");
+    assert_eq!(components[2]["parameters"][0]["coupon_code"], "25OFF");
+    assert_eq!(
+        components[3]["parameters"][0]["action"]["thumbnail_product_retailer_id"],
+        "sku-1"
+    );
+    assert_eq!(components[4]["parameters"][0]["payload"], "unsubscribe");
+
+    let media = serde_json::to_value(Parameter::from_image(Media::for_id("1"))).unwrap();
+    assert_eq!(media["type"], "image");
+    assert_eq!(media["image"]["id"], "1");
+}
+
+#[test]
+fn carousel_template() {
+    let cards = (0..2)
+        .map(|i| {
+            CarouselCard::new(
+                i,
+                vec![
+                    Component::with_parameters(
+                        ComponentType::Header,
+                        vec![Parameter::from_image(Media::for_id(&format!("media-{i}")))],
+                    ),
+                    Component::for_button(
+                        ComponentType::Button,
+                        ComponentSubType::QuickReply,
+                        vec![Parameter::from_payload(&format!("more-{i}"))],
+                        0,
+                    ),
+                ],
+            )
+        })
+        .collect();
+    let components = vec![
+        Component::with_parameters(ComponentType::Body, vec![Parameter::from_text("Jessica")]),
+        Component::carousel(cards),
+    ];
+    let template = Template::with_components("summer_carousel", "en_US", components);
+    let value = serde_json::to_value(template).unwrap();
+
+    let carousel = &value["components"][1];
+    assert_eq!(carousel["type"], "carousel");
+    assert_eq!(carousel["cards"][1]["card_index"], 1);
+    assert_eq!(carousel["cards"][1]["components"][0]["type"], "header");
+    assert_eq!(
+        carousel["cards"][1]["components"][0]["parameters"][0]["image"]["id"],
+        "media-1"
+    );
+    assert_eq!(
+        carousel["cards"][1]["components"][1]["parameters"][0]["payload"],
+        "more-1"
+    );
+}
+
+#[test]
+fn authentication_template() {
+    let value = serde_json::to_value(Template::authentication(
+        "verification_code",
+        "en_US",
+        "J$FpnYnP",
+    ))
+    .unwrap();
+    let components = &value["components"];
+    assert_eq!(components[0]["type"], "body");
+    assert_eq!(components[0]["parameters"][0]["text"], "J$FpnYnP");
+    assert_eq!(components[1]["type"], "button");
+    assert_eq!(components[1]["sub_type"], "url");
+    assert_eq!(components[1]["index"], 0);
+    assert_eq!(components[1]["parameters"][0]["text"], "J$FpnYnP");
+
+    // Authentication template as returned by the template management API
+    let definition: TemplateDefinition = serde_json::from_value(json!({
+        "name": "verification_code",
+        "language": "en_US",
+        "category": "AUTHENTICATION",
+        "components": [
+            { "type": "BODY", "text": "*{{1}}* is your verification code." },
+            { "type": "FOOTER", "text": "This code expires in 10 minutes." },
+            { "type": "BUTTONS", "buttons": [{
+                "type": "URL",
+                "text": "Copy code",
+                "url": "https://www.whatsapp.com/otp/code/?otp_type=COPY_CODE&code=otp{{1}}"
+            }] }
+        ]
+    }))
+    .unwrap();
+    let template = Template::authentication("verification_code", "en_US", "123456");
+    assert_eq!(template.validate_against(&definition), vec![]);
+
+    let definition = TemplateDefinition::authentication("verification_code", "en_US", Some(10));
+    assert_eq!(template.validate_against(&definition), vec![]);
+    assert_eq!(
+        Template::new("verification_code", "en_US").validate_against(&definition),
+        vec![
+            Violation::Missing {
+                field: "template.components.body".into()
+            },
+            Violation::Missing {
+                field: "template.components.buttons[0]".into()
+            }
+        ]
+    );
+
+    let value = serde_json::to_value(definition).unwrap();
+    assert_eq!(value["category"], "AUTHENTICATION");
+    assert_eq!(
+        value["components"],
+        json!([
+            { "type": "BODY" },
+            { "type": "FOOTER", "code_expiration_minutes": 10 },
+            { "type": "BUTTONS", "buttons": [{ "type": "OTP", "otp_type": "COPY_CODE" }] }
+        ])
+    );
+}
+
+const ORDER_SHIPPED_DEFINITION: &str = r#"{
+    "name": "order_shipped",
+    "language": "en_US",
+    "status": "APPROVED",
+    "category": "UTILITY",
+    "id": "594425479261596",
+    "components": [
+        { "type": "HEADER", "format": "IMAGE" },
+        { "type": "BODY", "text": "Hi {{1}}, order {{2}} has shipped. Total: {{3}}" },
+        { "type": "BUTTONS", "buttons": [
+            { "type": "URL", "text": "Track", "url": "https://example.com/track/{{1}}" },
+            { "type": "PHONE_NUMBER", "text": "Call us", "phone_number": "+15550001111" }
+        ] }
+    ]
+}"#;
+
+#[test]
+fn template_matches_its_definition() {
+    let definition: TemplateDefinition = serde_json::from_str(ORDER_SHIPPED_DEFINITION).unwrap();
+    let template = Template::with_components(
+        "order_shipped",
+        "en_US",
+        vec![
+            Component::with_parameters(
+                ComponentType::Header,
+                vec![Parameter::from_image(Media::new(
+                    "https://example.com/box.png",
+                ))],
+            ),
+            Component::with_parameters(
+                ComponentType::Body,
+                vec![
+                    Parameter::from_text("Pablo"),
+                    Parameter::from_text("#42"),
+                    Parameter::from_currency(Currency::new("$100.99", "USD", 100990)),
+                ],
+            ),
+            Component::for_button(
+                ComponentType::Button,
+                ComponentSubType::Url,
+                vec![Parameter::from_text("42")],
+                0,
+            ),
+        ],
+    );
+    assert_eq!(template.validate_against(&definition), vec![]);
+}
+
+#[test]
+fn template_mismatches_are_reported() {
+    let definition: TemplateDefinition = serde_json::from_str(ORDER_SHIPPED_DEFINITION).unwrap();
+    let template = Template::with_components(
+        "order_shipped",
+        "en_US",
+        vec![
+            Component::with_parameters(
+                ComponentType::Header,
+                vec![Parameter::from_text("Your order")],
+            ),
+            Component::with_parameters(
+                ComponentType::Body,
+                vec![
+                    Parameter::from_text("Pablo"),
+                    Parameter::from_text("#42"),
+                    Parameter::from_text("$100.99"),
+                    Parameter::from_text("extra"),
+                ],
+            ),
+            Component::for_button(
+                ComponentType::Button,
+                ComponentSubType::QuickReply,
+                vec![Parameter::from_payload("stop")],
+                2,
+            ),
+        ],
+    );
+    assert_eq!(
+        template.validate_against(&definition),
+        vec![
+            Violation::WrongType {
+                field: "template.components[0].parameters[0].type".into(),
+                expected: "image".into(),
+                actual: "text".into(),
+            },
+            Violation::Unexpected {
+                field: "template.components[1].parameters[3]".into(),
+            },
+            Violation::OutOfRange {
+                field: "template.components[2].index".into(),
+                max: 1,
+                actual: 2,
+            },
+            Violation::Missing {
+                field: "template.components.buttons[0]".into(),
+            },
+        ]
+    );
+
+    let template = Template::with_components(
+        "order_shipped",
+        "en_US",
+        vec![Component::with_parameters(
+            ComponentType::Body,
+            vec![Parameter::from_text("Pablo")],
+        )],
+    );
+    let fields: Vec<String> = template
+        .validate_against(&definition)
+        .iter()
+        .map(|violation| violation.field().to_string())
+        .collect();
+    assert_eq!(
+        fields,
+        [
+            "template.components[0].parameters[1]",
+            "template.components[0].parameters[2]",
+            "template.components.header",
+            "template.components.buttons[0]",
+        ]
+    );
+}
+
+#[test]
+fn template_renders_to_text_and_html() {
+    let definition: TemplateDefinition = serde_json::from_value(json!({
+        "name": "order_shipped",
+        "language": "en_US",
+        "category": "UTILITY",
+        "components": [
+            { "type": "HEADER", "format": "TEXT", "text": "Order {{1}}" },
+            { "type": "BODY", "text": "Hi {{1}}, your total of {{2}} ships on {{3}}.\nSee you {{4}}" },
+            { "type": "FOOTER", "text": "Reply STOP to opt out" },
+            { "type": "BUTTONS", "buttons": [
+                { "type": "URL", "text": "Track", "url": "https://example.com/track/{{1}}" },
+                { "type": "PHONE_NUMBER", "text": "Call us", "phone_number": "+15550001111" }
+            ] }
+        ]
+    }))
+    .unwrap();
+    let template = Template::with_components(
+        "order_shipped",
+        "en_US",
+        vec![
+            Component::with_parameters(ComponentType::Header, vec![Parameter::from_text("#42")]),
+            Component::with_parameters(
+                ComponentType::Body,
+                vec![
+                    Parameter::from_text("<Pablo>"),
+                    Parameter::from_currency(Currency::new("$100.99", "USD", 100990)),
+                    Parameter::from_date_time(DateTime::new("February 25, 1977")),
+                ],
+            ),
+            Component::for_button(
+                ComponentType::Button,
+                ComponentSubType::Url,
+                vec![Parameter::from_text("42")],
+                0,
+            ),
+        ],
+    );
+
+    let rendered = template.render(&definition);
+    assert_eq!(
+        rendered.buttons[0],
+        RenderedButton {
+            text: "Track".into(),
+            value: Some("https://example.com/track/42".into()),
+        }
+    );
+    assert_eq!(
+        rendered.to_text(),
+        "Order #42\n\n\
+         Hi <Pablo>, your total of $100.99 ships on February 25, 1977.\nSee you {{4}}\n\n\
+         Reply STOP to opt out\n\n\
+         [Track] https://example.com/track/42\n[Call us] +15550001111"
+    );
+
+    let html = rendered.to_html();
+    assert!(html.contains("<strong>Order #42</strong>"));
+    assert!(html.contains("Hi &lt;Pablo&gt;, your total of $100.99"));
+    assert!(html.contains("1977.<br>See you"));
+    assert!(html.contains(
+        "<a class=\"whatsapp-template-button\" href=\"https://example.com/track/42\">Track</a>"
+    ));
+    assert!(html.contains("<span class=\"whatsapp-template-button\">Call us: +15550001111</span>"));
+}
+
+#[test]
+fn authentication_template_renders_the_code() {
+    let definition = TemplateDefinition::authentication("verification_code", "en_US", Some(10));
+    let template = Template::authentication("verification_code", "en_US", "123456");
+    assert_eq!(
+        template.render(&definition).to_text(),
+        "123456 is your verification code.\n\n\
+         This code expires in 10 minutes.\n\n\
+         [Copy code] 123456"
+    );
+}
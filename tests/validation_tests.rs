@@ -0,0 +1,139 @@
+use whatsapp_business_cloud_api::{
+    models::{
+        Component, ComponentSubType, ComponentType, Interactive, InteractiveActionButton,
+        InteractiveActionSection, InteractiveActionSectionRow, Message, Parameter, Template, Text,
+        Violation,
+    },
+    WhatsappClient, WhatsappError,
+};
+
+#[test]
+fn valid_message_has_no_violations() {
+    let buttons = vec![
+        InteractiveActionButton::new("Yes", "yes"),
+        InteractiveActionButton::new("No", "no"),
+    ];
+    let interactive = Interactive::for_button(buttons, "Do you like Rust?");
+    let message = Message::from_interactive("16505555555", interactive, None);
+    assert!(message.validate().is_empty());
+}
+
+#[test]
+fn button_limits_are_reported() {
+    let buttons = vec![
+        InteractiveActionButton::new("A title that is way too long", "1"),
+        InteractiveActionButton::new("Two", "2"),
+        InteractiveActionButton::new("Three", "3"),
+        InteractiveActionButton::new("Four", "4"),
+    ];
+    let interactive = Interactive::for_button(buttons, "Pick one");
+    let violations = Message::from_interactive("16505555555", interactive, None).validate();
+
+    assert_eq!(
+        violations,
+        vec![
+            Violation::TooMany {
+                field: "interactive.action.buttons".into(),
+                max: 3,
+                actual: 4
+            },
+            Violation::TooLong {
+                field: "interactive.action.buttons[0].reply.title".into(),
+                max: 20,
+                actual: 28
+            },
+        ]
+    );
+}
+
+#[test]
+fn lengths_are_counted_in_characters() {
+    // 20 characters, but 40 bytes
+    let title = "ñ".repeat(20);
+    let buttons = vec![InteractiveActionButton::new(&title, "1")];
+    let interactive = Interactive::for_button(buttons, "Pick one");
+    assert!(Message::from_interactive("16505555555", interactive, None)
+        .validate()
+        .is_empty());
+
+    let text = Text::new(&"é".repeat(4097));
+    let violations = Message::from_text("16505555555", text, None).validate();
+    assert_eq!(violations[0].field(), "text.body");
+}
+
+#[test]
+fn list_limits_are_reported() {
+    let rows = (0..11)
+        .map(|i| InteractiveActionSectionRow::new(&i.to_string(), "Row"))
+        .collect();
+    let sections = vec![InteractiveActionSection::with_title(rows, "Section")];
+    let interactive = Interactive::for_list("Choose", sections, "Pick one");
+    let violations = Message::from_interactive("16505555555", interactive, None).validate();
+    assert_eq!(violations.len(), 1);
+    assert_eq!(violations[0].field(), "interactive.action.sections[].rows");
+}
+
+#[test]
+fn template_limits_are_reported() {
+    let components = vec![
+        Component::with_parameters(
+            ComponentType::Header,
+            vec![Parameter::from_text(&"x".repeat(61))],
+        ),
+        Component::for_button(
+            ComponentType::Button,
+            ComponentSubType::QuickReply,
+            vec![],
+            10,
+        ),
+    ];
+    let template = Template::with_components("order_shipped", "en_US", components);
+    let violations = Message::from_template("16505555555", template, None).validate();
+    let fields: Vec<&str> = violations.iter().map(Violation::field).collect();
+    assert_eq!(
+        fields,
+        vec![
+            "template.components[0].parameters[0].text",
+            "template.components[1].index"
+        ]
+    );
+}
+
+#[test]
+fn body_parameter_limit_depends_on_other_components() {
+    let body = || {
+        Component::with_parameters(
+            ComponentType::Body,
+            vec![Parameter::from_text(&"x".repeat(2000))],
+        )
+    };
+
+    let template = Template::with_components("order_shipped", "en_US", vec![body()]);
+    let violations = Message::from_template("16505555555", template, None).validate();
+    assert!(violations.is_empty(), "{violations:?}");
+
+    let components = vec![
+        Component::with_parameters(ComponentType::Header, vec![Parameter::from_text("Order")]),
+        body(),
+    ];
+    let template = Template::with_components("order_shipped", "en_US", components);
+    let violations = Message::from_template("16505555555", template, None).validate();
+    let fields: Vec<&str> = violations.iter().map(Violation::field).collect();
+    assert_eq!(fields, vec!["template.components[1].parameters[0].text"]);
+}
+
+#[tokio::test]
+async fn client_validates_messages_when_enabled() -> Result<(), WhatsappError> {
+    let client = WhatsappClient::builder("token")
+        .base_url("http://127.0.0.1:9")
+        .validate_messages(true)
+        .build()?;
+
+    let message = Message::from_text("16505555555", Text::new(""), None);
+    let error = client
+        .send_message("1234".into(), &message)
+        .await
+        .unwrap_err();
+    assert!(matches!(error, WhatsappError::ValidationError(violations) if violations.len() == 1));
+    Ok(())
+}
@@ -0,0 +1,77 @@
+use whatsapp_business_cloud_api::models::webhooks::{NotificationMessageType, NotificationPayload};
+
+fn notification(message: &str) -> NotificationPayload {
+    let json = format!(
+        r#"{{
+            "object": "whatsapp_business_account",
+            "entry": [{{
+                "id": "102290129340398",
+                "changes": [{{
+                    "value": {{
+                        "messaging_product": "whatsapp",
+                        "metadata": {{
+                            "display_phone_number": "15550783881",
+                            "phone_number_id": "106540352242922"
+                        }},
+                        "contacts": [{{ "profile": {{ "name": "Sheena Nelson" }}, "wa_id": "16505551234" }}],
+                        "messages": [{message}]
+                    }},
+                    "field": "messages"
+                }}]
+            }}]
+        }}"#
+    );
+    serde_json::from_str(&json).unwrap()
+}
+
+#[test]
+fn reaction_message_is_parsed() {
+    let payload = notification(
+        r#"{
+            "from": "16505551234",
+            "id": "wamid.HBgLMTY1MDM4Nzk0MzkVAgASGBQzQUFERjg0NDEzNDdFODU3MUMxMAA=",
+            "timestamp": "1698338454",
+            "reaction": {
+                "message_id": "wamid.HBgLMTY1MDM4Nzk0MzkVAgARGBI3QUZCMTdDRDlGM0Q1OEREMDcA",
+                "emoji": "😀"
+            },
+            "type": "reaction"
+        }"#,
+    );
+    let messages = payload.entry[0].changes[0].value.messages.as_ref().unwrap();
+    assert!(matches!(
+        messages[0].message_type,
+        NotificationMessageType::Reaction
+    ));
+    let reaction = messages[0].reaction.as_ref().unwrap();
+    assert_eq!(reaction.emoji.as_deref(), Some("😀"));
+}
+
+#[test]
+fn flow_reply_is_parsed() {
+    let payload = notification(
+        r#"{
+            "context": {
+                "from": "16315558151",
+                "id": "gBGGEiRVVgBPAgm7FUgc73noXjo"
+            },
+            "from": "16505551234",
+            "id": "wamid.HBgLMTY1MDM4Nzk0MzkVAgASGBQzQUFERjg0NDEzNDdFODU3MUMxMAA=",
+            "type": "interactive",
+            "interactive": {
+                "type": "nfm_reply",
+                "nfm_reply": {
+                    "response_json": "{\"flow_token\": \"AQAAAAACS5FpgQ_cAAAAAD0QI3s.\", \"date\": \"2024-05-01\"}",
+                    "body": "Sent",
+                    "name": "flow"
+                }
+            },
+            "timestamp": "1714510003"
+        }"#,
+    );
+    let messages = payload.entry[0].changes[0].value.messages.as_ref().unwrap();
+    let interactive = messages[0].interactive.as_ref().unwrap();
+    assert_eq!(interactive.interactive_type.as_deref(), Some("nfm_reply"));
+    let response = interactive.nfm_reply.as_ref().unwrap().response().unwrap();
+    assert_eq!(response["date"], "2024-05-01");
+}
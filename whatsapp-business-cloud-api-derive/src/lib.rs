@@ -0,0 +1,276 @@
+use std::collections::BTreeMap;
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote;
+use syn::{
+    parse::ParseStream, parse_macro_input, spanned::Spanned, Data, DeriveInput, Error, Ident,
+    LitInt, LitStr, Member, Token,
+};
+
+/// Derives `From<T> for Template`, building the components of a message template
+/// from the fields of a struct.
+///
+/// ```ignore
+/// #[derive(WhatsappTemplate)]
+/// #[template(name = "order_shipped", language = "en_US")]
+/// struct OrderShipped {
+///     #[header(image)]
+///     picture: Media,
+///     #[body(1)]
+///     name: String,
+///     #[body(2, currency)]
+///     total: Currency,
+///     #[button(0, url)]
+///     tracking: String,
+/// }
+/// ```
+///
+/// Field attributes:
+/// - `#[header]` a text header parameter, or `#[header(image | video | document | location)]`
+/// - `#[body(n)]` the parameter of the `{{n}}` body placeholder, as text. `#[body(n, currency)]`
+///   and `#[body(n, date_time)]` take a `Currency` or a `DateTime` field instead
+/// - `#[button(i, url | quick_reply | copy_code | flow | catalog | mpm)]` the parameter of the
+///   button at index `i`. Url, quick reply and copy code buttons take a text field, the others
+///   a `ParameterAction`
+///
+/// Text parameters accept any field implementing `ToString`.
+#[proc_macro_derive(WhatsappTemplate, attributes(template, header, body, button))]
+pub fn derive_whatsapp_template(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+struct ParameterField {
+    member: Member,
+    kind: Ident,
+}
+
+fn expand(input: DeriveInput) -> Result<TokenStream2, Error> {
+    let (name, language) = template_attribute(&input)?;
+
+    let fields = match &input.data {
+        Data::Struct(data) => &data.fields,
+        _ => {
+            return Err(Error::new(
+                Span::call_site(),
+                "WhatsappTemplate can only be derived for structs",
+            ))
+        }
+    };
+
+    let mut header: Option<ParameterField> = None;
+    let mut body: BTreeMap<u32, ParameterField> = BTreeMap::new();
+    let mut buttons: BTreeMap<u32, ParameterField> = BTreeMap::new();
+
+    for (i, field) in fields.iter().enumerate() {
+        let member = match &field.ident {
+            Some(ident) => Member::Named(ident.clone()),
+            None => Member::Unnamed(i.into()),
+        };
+        for attr in &field.attrs {
+            if attr.path().is_ident("header") {
+                let kind = if matches!(attr.meta, syn::Meta::Path(_)) {
+                    Ident::new("text", attr.span())
+                } else {
+                    attr.parse_args::<Ident>()?
+                };
+                check_kind(&kind, &["text", "image", "video", "document", "location"])?;
+                if header.is_some() {
+                    return Err(Error::new(attr.span(), "duplicate #[header] field"));
+                }
+                header = Some(ParameterField {
+                    member: member.clone(),
+                    kind,
+                });
+            } else if attr.path().is_ident("body") {
+                let (index, kind) = attr.parse_args_with(|input: ParseStream| {
+                    let index: LitInt = input.parse()?;
+                    let kind = if input.is_empty() {
+                        Ident::new("text", index.span())
+                    } else {
+                        input.parse::<Token![,]>()?;
+                        input.parse()?
+                    };
+                    Ok((index, kind))
+                })?;
+                check_kind(&kind, &["text", "currency", "date_time"])?;
+                let position: u32 = index.base10_parse()?;
+                if position == 0 {
+                    return Err(Error::new(
+                        index.span(),
+                        "body placeholders are numbered from 1",
+                    ));
+                }
+                let parameter = ParameterField {
+                    member: member.clone(),
+                    kind,
+                };
+                if body.insert(position, parameter).is_some() {
+                    return Err(Error::new(
+                        index.span(),
+                        format!("duplicate #[body({position})] field"),
+                    ));
+                }
+            } else if attr.path().is_ident("button") {
+                let (index, kind) = attr.parse_args_with(|input: ParseStream| {
+                    let index: LitInt = input.parse()?;
+                    input.parse::<Token![,]>()?;
+                    let kind: Ident = input.parse()?;
+                    Ok((index, kind))
+                })?;
+                check_kind(
+                    &kind,
+                    &["url", "quick_reply", "copy_code", "flow", "catalog", "mpm"],
+                )?;
+                let position: u32 = index.base10_parse()?;
+                let parameter = ParameterField {
+                    member: member.clone(),
+                    kind,
+                };
+                if buttons.insert(position, parameter).is_some() {
+                    return Err(Error::new(
+                        index.span(),
+                        format!("duplicate #[button({position}, ..)] field"),
+                    ));
+                }
+            }
+        }
+    }
+
+    // Positional parameters are matched by order, so a gap would shift every following one
+    for (expected, position) in (1..).zip(body.keys()) {
+        if *position != expected {
+            return Err(Error::new(
+                body[position].member.span(),
+                format!("missing #[body({expected})] field"),
+            ));
+        }
+    }
+
+    let models = quote!(::whatsapp_business_cloud_api::models);
+    let mut components = Vec::new();
+    if let Some(header) = &header {
+        let parameter = parameter(header);
+        components.push(quote! {
+            #models::Component::with_parameters(
+                #models::ComponentType::Header,
+                ::std::vec![#parameter],
+            )
+        });
+    }
+    if !body.is_empty() {
+        let parameters = body.values().map(parameter);
+        components.push(quote! {
+            #models::Component::with_parameters(
+                #models::ComponentType::Body,
+                ::std::vec![#(#parameters),*],
+            )
+        });
+    }
+    for (index, button) in &buttons {
+        let sub_type = match button.kind.to_string().as_str() {
+            "url" => quote!(Url),
+            "quick_reply" => quote!(QuickReply),
+            "copy_code" => quote!(CopyCode),
+            "flow" => quote!(Flow),
+            "catalog" => quote!(Catalog),
+            _ => quote!(Mpm),
+        };
+        let parameter = parameter(button);
+        let index = *index as i32;
+        components.push(quote! {
+            #models::Component::for_button(
+                #models::ComponentType::Button,
+                #models::ComponentSubType::#sub_type,
+                ::std::vec![#parameter],
+                #index,
+            )
+        });
+    }
+
+    let template = if components.is_empty() {
+        quote!(#models::Template::new(#name, #language))
+    } else {
+        quote!(#models::Template::with_components(#name, #language, ::std::vec![#(#components),*]))
+    };
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::core::convert::From<#ident #ty_generics> for #models::Template
+        #where_clause
+        {
+            #[allow(unused_variables)]
+            fn from(value: #ident #ty_generics) -> Self {
+                #template
+            }
+        }
+    })
+}
+
+fn template_attribute(input: &DeriveInput) -> Result<(LitStr, LitStr), Error> {
+    let mut name = None;
+    let mut language = None;
+    for attr in &input.attrs {
+        if !attr.path().is_ident("template") {
+            continue;
+        }
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("name") {
+                name = Some(meta.value()?.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("language") {
+                language = Some(meta.value()?.parse()?);
+                Ok(())
+            } else {
+                Err(meta.error("expected `name` or `language`"))
+            }
+        })?;
+    }
+
+    match (name, language) {
+        (Some(name), Some(language)) => Ok((name, language)),
+        _ => Err(Error::new(
+            Span::call_site(),
+            "missing #[template(name = \"...\", language = \"...\")] attribute",
+        )),
+    }
+}
+
+fn check_kind(kind: &Ident, allowed: &[&str]) -> Result<(), Error> {
+    if allowed.iter().any(|allowed| kind == allowed) {
+        Ok(())
+    } else {
+        Err(Error::new(
+            kind.span(),
+            format!("expected one of: {}", allowed.join(", ")),
+        ))
+    }
+}
+
+fn parameter(field: &ParameterField) -> TokenStream2 {
+    let models = quote!(::whatsapp_business_cloud_api::models);
+    let member = &field.member;
+    let value = quote!(value.#member);
+    match field.kind.to_string().as_str() {
+        "text" | "url" => {
+            quote!(#models::Parameter::from_text(&::std::string::ToString::to_string(&#value)))
+        }
+        "quick_reply" => {
+            quote!(#models::Parameter::from_payload(&::std::string::ToString::to_string(&#value)))
+        }
+        "copy_code" => quote!(#models::Parameter::from_coupon_code(
+            &::std::string::ToString::to_string(&#value)
+        )),
+        "currency" => quote!(#models::Parameter::from_currency(#value)),
+        "date_time" => quote!(#models::Parameter::from_date_time(#value)),
+        "image" => quote!(#models::Parameter::from_image(#value)),
+        "video" => quote!(#models::Parameter::from_video(#value)),
+        "document" => quote!(#models::Parameter::from_document(#value)),
+        "location" => quote!(#models::Parameter::from_location(#value)),
+        _ => quote!(#models::Parameter::from_action(#value)),
+    }
+}